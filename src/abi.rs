@@ -0,0 +1,165 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{N, PROCESS};
+use snarkvm::{
+    console::program::{Value, ValueType},
+    prelude::{Deserialize, Serialize},
+    synthesizer::program::{StackMatches, StackProgram},
+};
+
+use anyhow::{anyhow, ensure, Result};
+use core::str::FromStr;
+
+/// The visibility of a function input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// The input is a constant.
+    Constant,
+    /// The input is publicly visible on chain.
+    Public,
+    /// The input is encrypted on chain.
+    Private,
+    /// The input is a record, whose entries have their own visibility.
+    Record,
+}
+
+/// An input of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAbi {
+    /// The register of the input, i.e. `r0`.
+    pub register: String,
+    /// The type of the input, i.e. `address`, `u64`, or the name of a record.
+    pub type_name: String,
+    /// The visibility of the input.
+    pub visibility: Visibility,
+}
+
+/// A function of a program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionAbi {
+    /// The function name.
+    pub name: String,
+    /// The inputs of the function, in order.
+    pub inputs: Vec<InputAbi>,
+}
+
+/// A mapping of a program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingAbi {
+    /// The mapping name.
+    pub name: String,
+    /// The type of the keys.
+    pub key_type: String,
+    /// The type of the values.
+    pub value_type: String,
+}
+
+/// The functions and mappings of a program loaded in the process, i.e. to render a form for each function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramAbi {
+    /// The program ID.
+    pub program_id: String,
+    /// The functions of the program, in the order they are declared.
+    pub functions: Vec<FunctionAbi>,
+    /// The mappings of the program, in the order they are declared.
+    pub mappings: Vec<MappingAbi>,
+}
+
+impl ProgramAbi {
+    /// Returns the ABI of `credits.aleo`.
+    pub fn credits() -> Result<Self> {
+        Self::load("credits.aleo")
+    }
+
+    /// Returns the ABI of the given program, which must be loaded in the process.
+    pub fn load(program_id: &str) -> Result<Self> {
+        let program = PROCESS.get_program(program_id)?;
+
+        // Describe the functions.
+        let functions = program
+            .functions()
+            .values()
+            .map(|function| FunctionAbi {
+                name: function.name().to_string(),
+                inputs: function
+                    .inputs()
+                    .iter()
+                    .map(|input| {
+                        let (type_name, visibility) = match input.value_type() {
+                            ValueType::Constant(plaintext_type) => (plaintext_type.to_string(), Visibility::Constant),
+                            ValueType::Public(plaintext_type) => (plaintext_type.to_string(), Visibility::Public),
+                            ValueType::Private(plaintext_type) => (plaintext_type.to_string(), Visibility::Private),
+                            ValueType::Record(name) => (name.to_string(), Visibility::Record),
+                            ValueType::ExternalRecord(locator) => (locator.to_string(), Visibility::Record),
+                            ValueType::Future(locator) => (locator.to_string(), Visibility::Public),
+                        };
+                        InputAbi { register: input.register().to_string(), type_name, visibility }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        // Describe the mappings.
+        let mappings = program
+            .mappings()
+            .values()
+            .map(|mapping| MappingAbi {
+                name: mapping.name().to_string(),
+                key_type: mapping.key().plaintext_type().to_string(),
+                value_type: mapping.value().plaintext_type().to_string(),
+            })
+            .collect();
+
+        Ok(Self { program_id: program.id().to_string(), functions, mappings })
+    }
+
+    /// Returns the function with the given name, if it exists.
+    pub fn function(&self, name: &str) -> Option<&FunctionAbi> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    /// Returns the mapping with the given name, if it exists.
+    pub fn mapping(&self, name: &str) -> Option<&MappingAbi> {
+        self.mappings.iter().find(|mapping| mapping.name == name)
+    }
+
+    /// Parses the given inputs of the function, ensuring each one matches the type of its input, i.e. `5u64`.
+    pub fn parse_inputs(&self, function_name: &str, inputs: &[impl AsRef<str>]) -> Result<Vec<Value<N>>> {
+        let stack = PROCESS.get_stack(self.program_id.as_str())?;
+        let function = stack.get_function(&function_name.parse()?)?;
+        ensure!(
+            function.inputs().len() == inputs.len(),
+            "'{function_name}' takes {} inputs, found {}",
+            function.inputs().len(),
+            inputs.len()
+        );
+
+        // Parse each input, and ensure it matches its type.
+        function
+            .inputs()
+            .iter()
+            .zip(inputs)
+            .map(|(input, string)| {
+                let value = Value::<N>::from_str(string.as_ref())
+                    .map_err(|_| anyhow!("Invalid input '{}' for {}", string.as_ref(), input.register()))?;
+                stack
+                    .matches_value_type(&value, input.value_type())
+                    .map_err(|error| anyhow!("Invalid input for {}: {error}", input.register()))?;
+                Ok(value)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{secret::parse_private_key, SecretString, N};
+use snarkvm::console::account::{Address, PrivateKey, ViewKey};
+
+use anyhow::Result;
+use core::{fmt, str::FromStr};
+use rand::{CryptoRng, Rng};
+use zeroize::Zeroizing;
+
+/// An account, with its private key, view key, and address.
+///
+/// Its `private_key` may be passed directly as the private key of any `Credits` function.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Account {
+    /// The private key.
+    private_key: SecretString,
+    /// The view key.
+    view_key: Zeroizing<ViewKey<N>>,
+    /// The address.
+    address: Address<N>,
+}
+
+impl Account {
+    /// Samples a new account with a random private key.
+    pub fn new(rng: &mut (impl Rng + CryptoRng)) -> Result<Self> {
+        let private_key = Zeroizing::new(PrivateKey::<N>::new(rng)?);
+        Self::from_parsed(&private_key)
+    }
+
+    /// Initializes the account from the given private key.
+    pub fn from_private_key(private_key: &str) -> Result<Self> {
+        Self::from_parsed(&*parse_private_key(private_key)?)
+    }
+
+    /// Returns the private key, for use with `Credits`.
+    pub const fn private_key(&self) -> &SecretString {
+        &self.private_key
+    }
+
+    /// Returns the view key, i.e. to decrypt the records of the account.
+    pub fn view_key(&self) -> &ViewKey<N> {
+        &self.view_key
+    }
+
+    /// Returns the address.
+    pub const fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Initializes the account from the given parsed private key.
+    fn from_parsed(private_key: &PrivateKey<N>) -> Result<Self> {
+        Ok(Self {
+            private_key: SecretString::new(private_key.to_string()),
+            view_key: Zeroizing::new(ViewKey::try_from(private_key)?),
+            address: Address::try_from(private_key)?,
+        })
+    }
+}
+
+impl FromStr for Account {
+    type Err = anyhow::Error;
+
+    /// Initializes the account from the given private key.
+    fn from_str(private_key: &str) -> Result<Self> {
+        Self::from_private_key(private_key)
+    }
+}
+
+impl TryFrom<SecretString> for Account {
+    type Error = anyhow::Error;
+
+    /// Initializes the account from the given private key, i.e. one unlocked from a keystore or derived from a seed.
+    fn try_from(private_key: SecretString) -> Result<Self> {
+        Self::from_private_key(&private_key)
+    }
+}
+
+impl fmt::Debug for Account {
+    /// Redacts the private key and view key.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Account({})", self.address)
+    }
+}
+
+impl fmt::Display for Account {
+    /// Prints the address.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.address)
+    }
+}
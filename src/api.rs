@@ -0,0 +1,67 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::N;
+use snarkvm::{
+    console::network::Network,
+    ledger::block::{Block, Transaction},
+    prelude::DeserializeOwned,
+};
+
+use anyhow::{bail, Result};
+
+/// Returns the block at the given height from the given endpoint.
+pub(crate) fn get_block(endpoint: &str, height: u32) -> Result<Block<N>> {
+    get_request(&format!("{endpoint}/block/{height}"))
+}
+
+/// Returns the transaction with the given ID from the given endpoint.
+pub(crate) fn get_transaction<N: Network>(endpoint: &str, transaction_id: &str) -> Result<Transaction<N>> {
+    get_request(&format!("{endpoint}/transaction/{transaction_id}"))
+}
+
+/// Returns the ID of the confirmed transaction that contains the given transition ID, if it exists.
+pub(crate) fn find_transaction_id(endpoint: &str, transition_id: &str) -> Result<Option<String>> {
+    get_optional_request(&format!("{endpoint}/find/transactionID/{transition_id}"))
+}
+
+/// Performs a GET request to the given URL, returning the deserialized response, or `None` if it is not found.
+pub(crate) fn get_optional_request<T: DeserializeOwned>(url: &str) -> Result<Option<T>> {
+    // Send the request.
+    let response = reqwest::blocking::get(url)?;
+
+    // Ensure the response is successful.
+    match response.status() {
+        // Return the response.
+        status if status.is_success() => Ok(Some(response.json()?)),
+        // Return `None` if the resource is not found.
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        // Return the error.
+        _ => bail!(response.text()?),
+    }
+}
+
+/// Performs a GET request to the given URL, returning the deserialized response.
+pub(crate) fn get_request<T: DeserializeOwned>(url: &str) -> Result<T> {
+    // Send the request.
+    let response = reqwest::blocking::get(url)?;
+
+    // Ensure the response is successful.
+    match response.status().is_success() {
+        // Return the response.
+        true => Ok(response.json()?),
+        // Return the error.
+        false => bail!(response.text()?),
+    }
+}
@@ -12,11 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::API_URL;
+use crate::{api, ExecutionMode, Microcredits, RetryPolicy};
 use snarkvm::{
-    console::network::Network,
-    ledger::block::Transaction,
-    prelude::{de, Deserialize, DeserializeExt, Deserializer, Serialize, SerializeStruct, Serializer},
+    console::{
+        network::Network,
+        program::{Literal, Plaintext},
+        types::Field,
+    },
+    ledger::block::{Fee, Input, Transaction},
+    prelude::{
+        de,
+        error,
+        Deserialize,
+        DeserializeExt,
+        Deserializer,
+        FromBytes,
+        IoResult,
+        Read,
+        Serialize,
+        SerializeStruct,
+        Serializer,
+        ToBytes,
+        Write,
+        Zero,
+    },
     synthesizer::Authorization,
 };
 
@@ -32,32 +51,106 @@ pub struct Authorized<N: Network> {
     function: Authorization<N>,
     /// The authorization for the fee execution.
     fee: Option<Authorization<N>>,
-    /// Whether to broadcast the transaction.
-    broadcast: bool,
+    /// How to execute the authorization.
+    mode: ExecutionMode,
 }
 
 impl<N: Network> Authorized<N> {
     /// Initializes a new authorization.
-    pub const fn new(function: Authorization<N>, fee: Option<Authorization<N>>, broadcast: bool) -> Self {
-        Self { function, fee, broadcast }
+    pub const fn new(function: Authorization<N>, fee: Option<Authorization<N>>, mode: ExecutionMode) -> Self {
+        Self { function, fee, mode }
+    }
+
+    /// Returns the execution mode of the authorization.
+    pub const fn mode(&self) -> ExecutionMode {
+        self.mode
+    }
+
+    /// Returns the execution ID of the authorization, which the resulting transaction will contain.
+    pub fn execution_id(&self) -> Result<Field<N>> {
+        self.function.to_execution_id()
+    }
+
+    /// Returns the transition IDs of the function authorization, which the resulting transaction will contain.
+    ///
+    /// Both IDs are fixed once the authorization is signed, so they may be stored before it is executed.
+    pub fn transition_ids(&self) -> Vec<N::TransitionID> {
+        self.function.transitions().into_keys().collect()
+    }
+
+    /// Returns the ID of the fee transition, which the resulting transaction will contain, if the authorization pays a fee.
+    pub fn fee_transition_id(&self) -> Option<N::TransitionID> {
+        self.fee.as_ref().and_then(|fee| fee.transitions().into_keys().next())
+    }
+
+    /// Returns the ID of the transaction that executing the authorization will produce.
+    ///
+    /// The transaction ID only commits to the transition IDs of the execution and the fee, so it is fixed before
+    /// the transaction is proven.
+    pub fn transaction_id(&self) -> Result<N::TransactionID> {
+        // Retrieve the fee, whose global state root and proof are not part of the transaction ID.
+        let fee = match &self.fee {
+            Some(fee) => match fee.transitions().into_values().next() {
+                Some(transition) => Some(Fee::from(transition, N::StateRoot::from(Field::zero()), None)?),
+                None => bail!("The fee authorization has no transitions"),
+            },
+            None => None,
+        };
+        // Compute the transaction ID.
+        let transitions = self.function.transitions();
+        Ok((*Transaction::transitions_tree(transitions.values(), &fee)?.root()).into())
+    }
+
+    /// Returns the base fee of the authorization, which is zero if it pays no fee.
+    pub fn base_fee(&self) -> Result<Microcredits> {
+        self.fee_input(0)
+    }
+
+    /// Returns the priority fee of the authorization, which is zero if it pays no fee.
+    pub fn priority_fee(&self) -> Result<Microcredits> {
+        self.fee_input(1)
+    }
+
+    /// Returns the total fee of the authorization, i.e. to report the cost of a dry run.
+    pub fn total_fee(&self) -> Result<Microcredits> {
+        self.base_fee()?.checked_add(self.priority_fee()?)
+    }
+
+    /// Returns the ID of the transaction that contains this authorization at the given endpoint, if it is found.
+    pub fn find_transaction_id(&self, endpoint: &str) -> Result<Option<String>> {
+        match self.transition_ids().first() {
+            Some(transition_id) => api::find_transaction_id(endpoint, &transition_id.to_string()),
+            None => bail!("The authorization has no transitions"),
+        }
+    }
+
+    /// Returns the transaction that contains this authorization at the given endpoint, if it is found.
+    pub fn find_transaction(&self, endpoint: &str) -> Result<Option<Transaction<N>>> {
+        match self.find_transaction_id(endpoint)? {
+            Some(transaction_id) => Ok(Some(api::get_transaction(endpoint, &transaction_id)?)),
+            None => Ok(None),
+        }
     }
 
     /// Executes the authorization, returning the resulting transaction.
+    ///
+    /// This method retries with the default `RetryPolicy` for the public API endpoint.
     pub fn execute(self) -> Result<Transaction<N>> {
-        // Execute the authorization.
-        let response = reqwest::blocking::Client::new()
-            .post(format!("{API_URL}/execute"))
-            .header("Content-Type", "application/json")
-            .body(serde_json::to_string(&self)?)
-            .send()?;
-
-        // Ensure the response is successful.
-        match response.status().is_success() {
-            // Return the transaction.
-            true => Ok(response.json()?),
-            // Return the error.
-            false => bail!(response.text()?),
+        self.execute_with(&RetryPolicy::default())
+    }
+
+    /// Executes the authorization with the given retry policy, returning the resulting transaction.
+    ///
+    /// Every attempt resubmits this same authorization, so a retry never re-signs the transaction.
+    pub fn execute_with(self, policy: &RetryPolicy) -> Result<Transaction<N>> {
+        // Ensure the authorization is not a dry run.
+        if self.mode.is_dry_run() {
+            bail!("A dry run authorization is never executed, it would pay a fee of {}", self.total_fee()?);
         }
+        // Serialize the authorization once, for every attempt.
+        let body = serde_json::to_string(&self)?;
+        // Execute the authorization.
+        policy.post("/execute", &body)
     }
 
     /// Executes the authorization locally, returning the resulting transaction.
@@ -75,6 +168,106 @@ impl<N: Network> Authorized<N> {
         // Execute the transaction.
         vm.execute_authorization(self.function, self.fee, None, rng)
     }
+
+    /// Returns the transaction of the authorization, with the proof of the genesis block in place of its own proofs.
+    ///
+    /// Note: As its proofs are invalid, this method should *only* be used for *testing* how a transaction is handled.
+    #[cfg(test)]
+    pub fn execute_unproven(self) -> Result<Transaction<N>> {
+        use snarkvm::ledger::block::{Block, Execution};
+
+        // Retrieve the global state root and proof of an execution in the genesis block.
+        let genesis = Block::<N>::from_bytes_le(N::genesis_bytes())?;
+        let (global_state_root, proof) =
+            match genesis.transactions().iter().find_map(|confirmed| confirmed.transaction().execution()) {
+                Some(execution) => (execution.global_state_root(), execution.proof().cloned()),
+                None => bail!("The genesis block has no execution"),
+            };
+        // Construct the transaction.
+        let execution = Execution::from(self.function.transitions().into_values(), global_state_root, proof.clone())?;
+        let fee = match self.fee.and_then(|fee| fee.transitions().into_values().next()) {
+            Some(transition) => Some(Fee::from(transition, global_state_root, proof)?),
+            None => None,
+        };
+        Transaction::from_execution(execution, fee)
+    }
+}
+
+impl<N: Network> Authorized<N> {
+    /// Returns the amount at the given input of the fee transition, i.e. the base fee at 0 and the priority fee at 1.
+    fn fee_input(&self, index: usize) -> Result<Microcredits> {
+        let transition = match self.fee.as_ref().and_then(|fee| fee.transitions().into_values().next()) {
+            Some(transition) => transition,
+            None => return Ok(Microcredits::ZERO),
+        };
+        match transition.inputs().get(index) {
+            Some(Input::Public(_, Some(Plaintext::Literal(Literal::U64(amount), _)))) => {
+                Ok(Microcredits::new(**amount))
+            }
+            _ => bail!("The fee authorization has no public amount at input {index}"),
+        }
+    }
+}
+
+impl<N: Network> Clone for Authorized<N> {
+    /// Returns an independent replica of the authorization.
+    fn clone(&self) -> Self {
+        Self { function: self.function.replicate(), fee: self.fee.as_ref().map(|fee| fee.replicate()), mode: self.mode }
+    }
+}
+
+impl<N: Network> FromBytes for Authorized<N> {
+    /// Reads the authorization from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid authorized version"));
+        }
+        // Read the function authorization.
+        let function = Authorization::read_le(&mut reader)?;
+        // Read the fee authorization, if it exists.
+        let fee = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(Authorization::read_le(&mut reader)?),
+            _ => return Err(error("Invalid fee authorization variant")),
+        };
+        // Read the execution mode, whose first two variants match the former broadcast flag.
+        let mode = match u8::read_le(&mut reader)? {
+            0 => ExecutionMode::Prove,
+            1 => ExecutionMode::Broadcast,
+            2 => ExecutionMode::DryRun,
+            _ => return Err(error("Invalid execution mode")),
+        };
+        // Return the authorization.
+        Ok(Self { function, fee, mode })
+    }
+}
+
+impl<N: Network> ToBytes for Authorized<N> {
+    /// Writes the authorization to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the function authorization.
+        self.function.write_le(&mut writer)?;
+        // Write the fee authorization, if it exists.
+        match &self.fee {
+            None => 0u8.write_le(&mut writer)?,
+            Some(fee) => {
+                1u8.write_le(&mut writer)?;
+                fee.write_le(&mut writer)?;
+            }
+        }
+        // Write the execution mode.
+        match self.mode {
+            ExecutionMode::Prove => 0u8,
+            ExecutionMode::Broadcast => 1u8,
+            ExecutionMode::DryRun => 2u8,
+        }
+        .write_le(&mut writer)
+    }
 }
 
 impl<N: Network> Serialize for Authorized<N> {
@@ -85,7 +278,11 @@ impl<N: Network> Serialize for Authorized<N> {
         if let Some(fee) = &self.fee {
             authorization.serialize_field("fee", fee)?;
         }
-        authorization.serialize_field("broadcast", &self.broadcast)?;
+        // The execution API only reads the broadcast flag, so a dry run is marked separately.
+        authorization.serialize_field("broadcast", &self.mode.is_broadcast())?;
+        if self.mode.is_dry_run() {
+            authorization.serialize_field("dry_run", &true)?;
+        }
         authorization.end()
     }
 }
@@ -101,8 +298,14 @@ impl<'de, N: Network> Deserialize<'de> for Authorized<N> {
         let fee = serde_json::from_value(authorization.get_mut("fee").unwrap_or(&mut serde_json::Value::Null).take())
             .map_err(de::Error::custom)?;
         // Retrieve the broadcast flag.
-        let broadcast = DeserializeExt::take_from_value::<D>(&mut authorization, "broadcast")?;
+        let broadcast: bool = DeserializeExt::take_from_value::<D>(&mut authorization, "broadcast")?;
+        // Retrieve the execution mode.
+        let mode = match (authorization.get("dry_run").and_then(serde_json::Value::as_bool), broadcast) {
+            (Some(true), _) => ExecutionMode::DryRun,
+            (_, true) => ExecutionMode::Broadcast,
+            (_, false) => ExecutionMode::Prove,
+        };
         // Recover the authorization.
-        Ok(Self { function, fee, broadcast })
+        Ok(Self { function, fee, mode })
     }
 }
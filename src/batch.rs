@@ -0,0 +1,180 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    get_public_balance,
+    get_total_cost_in_microcredits,
+    parallel::par_authorize,
+    secret::address_of,
+    Authorized,
+    Credits,
+    ExecutionMode,
+    Microcredits,
+    N,
+};
+use snarkvm::{
+    console::account::Address,
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use core::str::FromStr;
+use rand::{CryptoRng, Rng};
+
+/// A single payout of a batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    /// The address of the recipient.
+    pub recipient: String,
+    /// The amount in microcredits.
+    pub amount_in_microcredits: Microcredits,
+}
+
+/// A batch of `transfer_public` payouts from a single sender.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payouts {
+    /// The payouts.
+    payouts: Vec<Payout>,
+}
+
+impl Payouts {
+    /// Initializes a new batch from the given payouts.
+    pub fn new(payouts: Vec<Payout>) -> Self {
+        Self { payouts }
+    }
+
+    /// Initializes a new batch from CSV lines of the form `recipient,amount`, with an optional
+    /// `recipient,amount` header. Each amount is parsed as `Microcredits`, i.e. `1.5 credits` or `1500000`.
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut payouts = Vec::new();
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            // Skip empty lines and the header.
+            if line.is_empty() || (index == 0 && line.eq_ignore_ascii_case("recipient,amount")) {
+                continue;
+            }
+            // Parse the recipient and amount.
+            let (recipient, amount) = match line.split(',').collect::<Vec<_>>()[..] {
+                [recipient, amount] => (recipient.trim(), amount.trim()),
+                _ => bail!("Line {} of the CSV must be of the form 'recipient,amount'", index + 1),
+            };
+            let amount_in_microcredits = Microcredits::from_str(amount)
+                .map_err(|error| anyhow!("Line {} of the CSV has an invalid amount: {error}", index + 1))?;
+            payouts.push(Payout { recipient: recipient.to_string(), amount_in_microcredits });
+        }
+        Ok(Self { payouts })
+    }
+
+    /// Returns the payouts.
+    pub fn payouts(&self) -> &[Payout] {
+        &self.payouts
+    }
+
+    /// Returns the number of payouts.
+    pub fn len(&self) -> usize {
+        self.payouts.len()
+    }
+
+    /// Returns `true` if there are no payouts.
+    pub fn is_empty(&self) -> bool {
+        self.payouts.is_empty()
+    }
+
+    /// Ensures every recipient is a valid address, reporting every invalid recipient at once.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = self
+            .payouts
+            .iter()
+            .enumerate()
+            .filter(|(_, payout)| Address::<N>::from_str(&payout.recipient).is_err())
+            .map(|(index, payout)| format!("#{index} '{}'", payout.recipient))
+            .collect::<Vec<_>>();
+        ensure!(invalid.is_empty(), "Found invalid recipients: {}", invalid.join(", "));
+        Ok(())
+    }
+
+    /// Returns the total cost of the batch, including the base fee and priority fee of every payout.
+    pub fn total_cost(&self, priority_fee_in_microcredits: impl Into<Microcredits>) -> Result<Microcredits> {
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
+        let costs = self
+            .payouts
+            .iter()
+            .map(|payout| {
+                get_total_cost_in_microcredits(
+                    "credits.aleo",
+                    "transfer_public",
+                    payout.amount_in_microcredits,
+                    priority_fee_in_microcredits,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Microcredits::checked_sum(costs)
+    }
+
+    /// Returns the `transfer_public` authorizations for every payout, authorized in parallel.
+    ///
+    /// Before authorizing, this method validates every recipient, and checks the total cost of the batch
+    /// against the sender's public balance at the given API endpoint. The result of each payout is reported
+    /// to `on_progress` as it completes.
+    pub fn authorize(
+        &self,
+        endpoint: &str,
+        private_key: &str,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+        on_progress: impl Fn(usize, &Result<Authorized<N>>) + Sync,
+    ) -> Result<Vec<Result<Authorized<N>>>> {
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
+
+        // Ensure every recipient is valid.
+        self.validate()?;
+        // Compute the total cost of the batch.
+        let total_cost_in_microcredits = self.total_cost(priority_fee_in_microcredits)?;
+        // Retrieve the sender's public balance.
+        let sender = address_of(private_key)?;
+        let balance_in_microcredits = get_public_balance(endpoint, &sender.to_string())?;
+        // Ensure the balance covers the total cost.
+        ensure!(
+            balance_in_microcredits >= total_cost_in_microcredits,
+            "The public balance of {balance_in_microcredits} is insufficient to cover the batch cost of {total_cost_in_microcredits}"
+        );
+
+        Ok(self.authorize_unchecked(private_key, priority_fee_in_microcredits, mode, rng, on_progress))
+    }
+
+    /// Returns the `transfer_public` authorizations for every payout, authorized in parallel,
+    /// without checking the sender's public balance.
+    pub(crate) fn authorize_unchecked(
+        &self,
+        private_key: &str,
+        priority_fee_in_microcredits: Microcredits,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+        on_progress: impl Fn(usize, &Result<Authorized<N>>) + Sync,
+    ) -> Vec<Result<Authorized<N>>> {
+        par_authorize(&self.payouts, rng, |index, payout, rng| {
+            let result = Credits::transfer_public(
+                private_key,
+                &payout.recipient,
+                payout.amount_in_microcredits,
+                priority_fee_in_microcredits,
+                mode,
+                rng,
+            );
+            on_progress(index, &result);
+            result
+        })
+    }
+}
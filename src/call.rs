@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Authorized, Credits, ExecutionMode, FeeEstimator, FeeLevel, Microcredits, N};
+use snarkvm::prelude::{Deserialize, Serialize};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+
+/// A call to a `credits.aleo` function, with its inputs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "snake_case")]
+pub enum CreditsCall {
+    /// A call to `bond_public`.
+    BondPublic { validator: String, amount_in_microcredits: Microcredits },
+    /// A call to `unbond_public`.
+    UnbondPublic { amount_in_microcredits: Microcredits },
+    /// A call to `unbond_delegator_as_validator`.
+    UnbondDelegatorAsValidator { delegator: String },
+    /// A call to `claim_unbond_public`.
+    ClaimUnbondPublic,
+    /// A call to `set_validator_state`.
+    SetValidatorState { is_open: bool },
+    /// A call to `transfer_public`.
+    TransferPublic { recipient: String, amount_in_microcredits: Microcredits },
+    /// A call to `transfer_public_to_private`.
+    TransferPublicToPrivate { recipient: String, amount_in_microcredits: Microcredits },
+}
+
+impl CreditsCall {
+    /// Returns the function name of the call.
+    pub const fn function_name(&self) -> &'static str {
+        match self {
+            Self::BondPublic { .. } => "bond_public",
+            Self::UnbondPublic { .. } => "unbond_public",
+            Self::UnbondDelegatorAsValidator { .. } => "unbond_delegator_as_validator",
+            Self::ClaimUnbondPublic => "claim_unbond_public",
+            Self::SetValidatorState { .. } => "set_validator_state",
+            Self::TransferPublic { .. } => "transfer_public",
+            Self::TransferPublicToPrivate { .. } => "transfer_public_to_private",
+        }
+    }
+
+    /// Returns the authorization for the call, by dispatching to the corresponding `Credits` function.
+    pub fn authorize(
+        &self,
+        private_key: &str,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<Authorized<N>> {
+        let priority_fee = priority_fee_in_microcredits.into();
+        match self {
+            Self::BondPublic { validator, amount_in_microcredits } => {
+                Credits::bond_public(private_key, validator, *amount_in_microcredits, priority_fee, mode, rng)
+            }
+            Self::UnbondPublic { amount_in_microcredits } => {
+                Credits::unbond_public(private_key, *amount_in_microcredits, priority_fee, mode, rng)
+            }
+            Self::UnbondDelegatorAsValidator { delegator } => {
+                Credits::unbond_delegator_as_validator(private_key, delegator, priority_fee, mode, rng)
+            }
+            Self::ClaimUnbondPublic => Credits::claim_unbond_public(private_key, priority_fee, mode, rng),
+            Self::SetValidatorState { is_open } => {
+                Credits::set_validator_state(private_key, *is_open, priority_fee, mode, rng)
+            }
+            Self::TransferPublic { recipient, amount_in_microcredits } => {
+                Credits::transfer_public(private_key, recipient, *amount_in_microcredits, priority_fee, mode, rng)
+            }
+            Self::TransferPublicToPrivate { recipient, amount_in_microcredits } => Credits::transfer_public_to_private(
+                private_key,
+                recipient,
+                *amount_in_microcredits,
+                priority_fee,
+                mode,
+                rng,
+            ),
+        }
+    }
+
+    /// Returns the authorization for the call, with the priority fee suggested by the estimator at the fee level.
+    pub fn authorize_with_fee_level(
+        &self,
+        private_key: &str,
+        estimator: &FeeEstimator,
+        fee_level: FeeLevel,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<Authorized<N>> {
+        self.authorize(private_key, estimator.suggest(fee_level)?, mode, rng)
+    }
+}
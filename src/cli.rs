@@ -0,0 +1,252 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{CreditsCall, ExecutionMode, Keystore, Microcredits, RetryPolicy, SecretString, API_URL};
+use snarkvm::{console::account::Address, prelude::Serialize};
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use core::str::FromStr;
+use std::{fs, path::PathBuf};
+
+/// The default environment variable to read the private key from.
+pub const DEFAULT_PRIVATE_KEY_ENV: &str = "CREDITS_PRIVATE_KEY";
+/// The default environment variable to read the keystore password from.
+pub const DEFAULT_KEYSTORE_PASSWORD_ENV: &str = "CREDITS_KEYSTORE_PASSWORD";
+
+/// A command-line wallet for the functions of `credits.aleo`.
+#[derive(Debug, Parser)]
+#[clap(name = "credits", version, about)]
+pub struct Cli {
+    /// The API endpoint to submit transactions to.
+    #[clap(long, global = true, default_value = API_URL)]
+    endpoint: String,
+    /// The output format.
+    #[clap(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+    /// Authorizes the call and prints a summary, without executing it or writing the authorization.
+    #[clap(long, global = true)]
+    dry_run: bool,
+    /// The priority fee, i.e. `0.01 credits` or `10000`.
+    #[clap(long, global = true, default_value = "0")]
+    priority_fee: Microcredits,
+    /// The source of the private key.
+    #[clap(flatten)]
+    key: KeySource,
+    /// The command to run.
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// The output format of the CLI.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// JSON.
+    Json,
+}
+
+/// The source of the private key, which is the keystore if one is given, then the private key file if one is given,
+/// and the environment variable otherwise.
+#[derive(Debug, Args)]
+struct KeySource {
+    /// The environment variable to read the private key from.
+    #[clap(long, global = true, default_value = DEFAULT_PRIVATE_KEY_ENV)]
+    private_key_env: String,
+    /// The file to read the private key from.
+    #[clap(long, global = true)]
+    private_key_file: Option<PathBuf>,
+    /// The keystore directory to unlock the private key from, with the password in the password environment variable.
+    #[clap(long, global = true, requires = "address")]
+    keystore: Option<PathBuf>,
+    /// The address of the private key to unlock from the keystore.
+    #[clap(long, global = true)]
+    address: Option<String>,
+    /// The environment variable to read the keystore password from.
+    #[clap(long, global = true, default_value = DEFAULT_KEYSTORE_PASSWORD_ENV)]
+    password_env: String,
+}
+
+impl KeySource {
+    /// Returns the private key from the source.
+    fn private_key(&self) -> Result<SecretString> {
+        match (&self.keystore, &self.address, &self.private_key_file) {
+            (Some(directory), Some(address), _) => {
+                let password = SecretString::from_env(&self.password_env)
+                    .map_err(|_| anyhow!("Missing the keystore password, set '{}'", self.password_env))?;
+                Keystore::open(directory)?.unlock(&Address::from_str(address)?, &password)
+            }
+            (Some(_), None, _) => bail!("Missing the '--address' to unlock from the keystore"),
+            (None, _, Some(path)) => SecretString::from_file(path),
+            (None, _, None) => SecretString::from_env(&self.private_key_env).map_err(|_| {
+                anyhow!("Missing the private key, set '{}' or pass '--private-key-file'", self.private_key_env)
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Authorizes a call, and writes the authorization as JSON without executing it.
+    Authorize {
+        /// The file to write the authorization to, instead of stdout.
+        #[clap(long, global = true)]
+        out: Option<PathBuf>,
+        /// The call to authorize.
+        #[clap(subcommand)]
+        call: Call,
+    },
+    #[clap(flatten)]
+    Call(Call),
+}
+
+#[derive(Debug, Subcommand)]
+enum Call {
+    /// Bonds microcredits to a validator.
+    BondPublic {
+        /// The address of the validator.
+        validator: String,
+        /// The amount to bond, i.e. `10 credits` or `10000000`.
+        amount: Microcredits,
+    },
+    /// Unbonds microcredits from the staker's validator.
+    UnbondPublic {
+        /// The amount to unbond, i.e. `10 credits` or `10000000`.
+        amount: Microcredits,
+    },
+    /// Unbonds all microcredits of a delegator, as their validator.
+    UnbondDelegatorAsValidator {
+        /// The address of the delegator.
+        delegator: String,
+    },
+    /// Claims the unbonded microcredits of the staker.
+    ClaimUnbondPublic,
+    /// Opens or closes the validator to new delegators.
+    SetValidatorState {
+        /// Whether the validator is open to new delegators.
+        #[clap(action = clap::ArgAction::Set)]
+        is_open: bool,
+    },
+    /// Transfers public microcredits to a recipient.
+    TransferPublic {
+        /// The address of the recipient.
+        recipient: String,
+        /// The amount to transfer, i.e. `1.5 credits` or `1500000`.
+        amount: Microcredits,
+    },
+    /// Transfers public microcredits to a private record owned by a recipient.
+    TransferPublicToPrivate {
+        /// The address of the recipient.
+        recipient: String,
+        /// The amount to transfer, i.e. `1.5 credits` or `1500000`.
+        amount: Microcredits,
+    },
+}
+
+impl From<Call> for CreditsCall {
+    /// Returns the `credits.aleo` call for the subcommand.
+    fn from(call: Call) -> Self {
+        match call {
+            Call::BondPublic { validator, amount } => Self::BondPublic { validator, amount_in_microcredits: amount },
+            Call::UnbondPublic { amount } => Self::UnbondPublic { amount_in_microcredits: amount },
+            Call::UnbondDelegatorAsValidator { delegator } => Self::UnbondDelegatorAsValidator { delegator },
+            Call::ClaimUnbondPublic => Self::ClaimUnbondPublic,
+            Call::SetValidatorState { is_open } => Self::SetValidatorState { is_open },
+            Call::TransferPublic { recipient, amount } => {
+                Self::TransferPublic { recipient, amount_in_microcredits: amount }
+            }
+            Call::TransferPublicToPrivate { recipient, amount } => {
+                Self::TransferPublicToPrivate { recipient, amount_in_microcredits: amount }
+            }
+        }
+    }
+}
+
+/// A summary of an authorized call, printed on a dry run.
+#[derive(Debug, Serialize)]
+struct Summary {
+    /// The function name of the call.
+    function: &'static str,
+    /// The execution ID of the authorization.
+    execution_id: String,
+    /// The base fee of the call.
+    base_fee: Microcredits,
+    /// The priority fee of the call.
+    priority_fee: Microcredits,
+}
+
+impl Cli {
+    /// Runs the command, returning the output to print.
+    pub fn run(self) -> Result<String> {
+        // Split the command into the call and the authorization output, if any.
+        let (call, out) = match self.command {
+            Command::Authorize { call, out } => (CreditsCall::from(call), Some(out)),
+            Command::Call(call) => (CreditsCall::from(call), None),
+        };
+
+        // Authorize the call.
+        let private_key = self.key.private_key()?;
+        let mode = match self.dry_run {
+            true => ExecutionMode::DryRun,
+            false => ExecutionMode::Broadcast,
+        };
+        let authorization = call.authorize(&private_key, self.priority_fee, mode, &mut rand::thread_rng())?;
+
+        // Print a summary on a dry run.
+        if self.dry_run {
+            let summary = Summary {
+                function: call.function_name(),
+                execution_id: authorization.execution_id()?.to_string(),
+                base_fee: authorization.base_fee()?,
+                priority_fee: authorization.priority_fee()?,
+            };
+            return match self.output {
+                OutputFormat::Text => Ok(format!(
+                    "Dry run of '{}' with execution ID '{}', a base fee of {:#}, and a priority fee of {:#}",
+                    summary.function, summary.execution_id, summary.base_fee, summary.priority_fee
+                )),
+                OutputFormat::Json => Ok(serde_json::to_string_pretty(&summary)?),
+            };
+        }
+
+        match out {
+            // Write the authorization.
+            Some(Some(path)) => {
+                fs::write(&path, serde_json::to_string_pretty(&authorization)?)?;
+                match self.output {
+                    OutputFormat::Text => Ok(format!("Wrote the authorization to '{}'", path.display())),
+                    OutputFormat::Json => Ok(serde_json::json!({ "path": path }).to_string()),
+                }
+            }
+            // Print the authorization, which is always JSON.
+            Some(None) => Ok(serde_json::to_string_pretty(&authorization)?),
+            // Execute the authorization.
+            None => {
+                let transaction = authorization.execute_with(&RetryPolicy::new(&self.endpoint))?;
+                match self.output {
+                    OutputFormat::Text => {
+                        Ok(format!("Executed '{}' in transaction '{}'", call.function_name(), transaction.id()))
+                    }
+                    OutputFormat::Json => Ok(serde_json::to_string_pretty(&transaction)?),
+                }
+            }
+        }
+    }
+
+    /// Returns the output format.
+    pub const fn output(&self) -> OutputFormat {
+        self.output
+    }
+}
@@ -20,12 +20,16 @@ pub type A = snarkvm::circuit::AleoV0;
 pub const API_URL: &str = "https://api.explorer.aleo.org/v1/testnet3/explorer";
 // pub const API_URL: &str = "http://127.0.0.1:6130/v1/testnet3/explorer";
 
+use crate::Microcredits;
 use snarkvm::synthesizer::Process;
 
 use anyhow::{bail, Result};
 
 lazy_static! {
     /// The main process.
+    ///
+    /// The process is `Send + Sync`, and `Process::authorize` only requires a shared reference,
+    /// so authorizations may be generated concurrently from any number of threads (see `Credits::authorize_many`).
     pub(crate) static ref PROCESS: Process<N> = Process::<N>::load().unwrap();
 }
 
@@ -34,6 +38,7 @@ pub fn get_base_fee_in_microcredits(program_id: &str, function_name: &str) -> Re
     match (program_id, function_name) {
         ("credits.aleo", "bond_public") => Ok(843880),
         ("credits.aleo", "claim_unbond_public") => Ok(167230),
+        ("credits.aleo", "join") => Ok(1423),
         ("credits.aleo", "set_validator_state") => Ok(128275),
         ("credits.aleo", "transfer_public") => Ok(263388),
         ("credits.aleo", "transfer_public_to_private") => Ok(136587),
@@ -42,3 +47,17 @@ pub fn get_base_fee_in_microcredits(program_id: &str, function_name: &str) -> Re
         _ => bail!("Unknown program ID '{program_id}' and function name '{function_name}'"),
     }
 }
+
+/// Retrieves the total cost in microcredits for a given program and function,
+/// including the amount being sent and the priority fee.
+pub fn get_total_cost_in_microcredits(
+    program_id: &str,
+    function_name: &str,
+    amount_in_microcredits: Microcredits,
+    priority_fee_in_microcredits: Microcredits,
+) -> Result<Microcredits> {
+    // Retrieve the base fee in microcredits.
+    let base_fee_in_microcredits = Microcredits::new(get_base_fee_in_microcredits(program_id, function_name)?);
+    // Compute the total cost.
+    amount_in_microcredits.checked_add(base_fee_in_microcredits)?.checked_add(priority_fee_in_microcredits)
+}
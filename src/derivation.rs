@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{SecretString, N};
+use snarkvm::{
+    console::{
+        account::{Address, PrivateKey},
+        network::Environment,
+        types::Field,
+    },
+    fields::PrimeField,
+};
+
+use anyhow::{ensure, Result};
+use core::{fmt, ops::Range};
+use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
+
+/// The domain separator of the account derivation.
+pub const DERIVATION_DOMAIN: &str = "AleoCreditsAccountDerivation0";
+
+/// A master seed, from which any number of accounts are deterministically derived by index.
+///
+/// The account at index `i` has the account seed
+/// `SHA-512(DERIVATION_DOMAIN || len(master_seed) as u8 || master_seed || i as u32 LE)`,
+/// read as a little-endian integer modulo the order of the base field, from which the private key is derived
+/// as in `PrivateKey::try_from`. The same master seed always yields the same accounts.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MasterSeed(Zeroizing<Vec<u8>>);
+
+impl MasterSeed {
+    /// The maximum number of bytes in a master seed.
+    pub const MAX_SIZE_IN_BYTES: usize = 64;
+    /// The minimum number of bytes in a master seed.
+    pub const MIN_SIZE_IN_BYTES: usize = 32;
+
+    /// Samples a new random 32-byte master seed.
+    pub fn new(rng: &mut (impl Rng + CryptoRng)) -> Self {
+        Self(Zeroizing::new(rng.gen::<[u8; 32]>().to_vec()))
+    }
+
+    /// Initializes the master seed from the given bytes, which must be 32 to 64 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            (Self::MIN_SIZE_IN_BYTES..=Self::MAX_SIZE_IN_BYTES).contains(&bytes.len()),
+            "The master seed must be {} to {} bytes, found {} bytes",
+            Self::MIN_SIZE_IN_BYTES,
+            Self::MAX_SIZE_IN_BYTES,
+            bytes.len()
+        );
+        Ok(Self(Zeroizing::new(bytes.to_vec())))
+    }
+
+    /// Initializes the master seed from the given hex string.
+    pub fn from_hex(seed: &str) -> Result<Self> {
+        Self::from_bytes(&Zeroizing::new(hex::decode(seed.trim())?))
+    }
+
+    /// Returns the master seed as a hex string, i.e. to back it up.
+    pub fn to_hex(&self) -> SecretString {
+        SecretString::new(hex::encode(&*self.0))
+    }
+
+    /// Returns the private key at the given index, for use with `Credits`.
+    pub fn private_key(&self, index: u32) -> Result<SecretString> {
+        Ok(SecretString::new(self.derive(index)?.to_string()))
+    }
+
+    /// Returns the address at the given index.
+    pub fn address(&self, index: u32) -> Result<Address<N>> {
+        Address::try_from(&*self.derive(index)?)
+    }
+
+    /// Returns the index of the given address within the given range of indices, if it is found.
+    pub fn find(&self, address: &Address<N>, indices: Range<u32>) -> Result<Option<u32>> {
+        for index in indices {
+            if self.address(index)? == *address {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Derives the private key at the given index.
+    pub(crate) fn derive(&self, index: u32) -> Result<Zeroizing<PrivateKey<N>>> {
+        // Hash the domain, master seed, and index.
+        let mut hasher = Sha512::new();
+        hasher.update(DERIVATION_DOMAIN.as_bytes());
+        hasher.update([self.0.len() as u8]);
+        hasher.update(&*self.0);
+        hasher.update(index.to_le_bytes());
+        let digest = Zeroizing::new(hasher.finalize().to_vec());
+        // Reduce the digest into the account seed.
+        let seed = Field::<N>::new(<N as Environment>::Field::from_bytes_le_mod_order(&digest));
+        // Derive the private key.
+        Ok(Zeroizing::new(PrivateKey::try_from(seed)?))
+    }
+}
+
+impl fmt::Debug for MasterSeed {
+    /// Redacts the master seed.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MasterSeed(***)")
+    }
+}
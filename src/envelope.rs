@@ -0,0 +1,169 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Authorized;
+use snarkvm::{
+    console::network::Network,
+    prelude::{FromBytes, ToBytes},
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+/// The magic bytes that begin an envelope.
+pub const ENVELOPE_MAGIC: [u8; 4] = *b"AUTH";
+/// The version of the envelope format.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// The number of bytes in the header of an envelope, i.e. the magic bytes, version, network ID, and payload length.
+const HEADER_SIZE_IN_BYTES: usize = 4 + 1 + 2 + 4;
+/// The number of bytes in the checksum of an envelope.
+const CHECKSUM_SIZE_IN_BYTES: usize = 4;
+/// The alphabet of the QR encoding, which is RFC 4648 base32 and fits the alphanumeric mode of a QR code.
+const QR_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+impl<N: Network> Authorized<N> {
+    /// Returns the authorization in a versioned binary envelope.
+    ///
+    /// The envelope is `ENVELOPE_MAGIC || ENVELOPE_VERSION || network ID (u16 LE) || length (u32 LE) || payload || checksum`,
+    /// where the payload is the authorization in bytes, and the checksum is the first 4 bytes of the SHA-256 of
+    /// everything before it.
+    pub fn to_envelope(&self) -> Result<Vec<u8>> {
+        let payload = self.to_bytes_le()?;
+        // Write the header and payload.
+        let mut envelope = Vec::with_capacity(HEADER_SIZE_IN_BYTES + payload.len() + CHECKSUM_SIZE_IN_BYTES);
+        envelope.extend_from_slice(&ENVELOPE_MAGIC);
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&N::ID.to_le_bytes());
+        envelope.extend_from_slice(&u32::try_from(payload.len())?.to_le_bytes());
+        envelope.extend_from_slice(&payload);
+        // Write the checksum.
+        let checksum = checksum(&envelope);
+        envelope.extend_from_slice(&checksum);
+        Ok(envelope)
+    }
+
+    /// Returns the authorization from the given envelope, ensuring it is for this network.
+    pub fn from_envelope(envelope: &[u8]) -> Result<Self> {
+        ensure!(envelope.len() >= HEADER_SIZE_IN_BYTES + CHECKSUM_SIZE_IN_BYTES, "The envelope is too short");
+        // Ensure the checksum matches.
+        let (body, expected) = envelope.split_at(envelope.len() - CHECKSUM_SIZE_IN_BYTES);
+        ensure!(checksum(body) == expected, "The envelope checksum does not match");
+
+        // Ensure the header is valid.
+        let (header, payload) = body.split_at(HEADER_SIZE_IN_BYTES);
+        ensure!(header[0..4] == ENVELOPE_MAGIC, "The data is not an authorization envelope");
+        ensure!(header[4] == ENVELOPE_VERSION, "Unsupported envelope version {}", header[4]);
+        let network_id = u16::from_le_bytes([header[5], header[6]]);
+        ensure!(network_id == N::ID, "The envelope is for network {network_id}, expected network {}", N::ID);
+        let length = u32::from_le_bytes([header[7], header[8], header[9], header[10]]);
+        ensure!(payload.len() == length as usize, "The envelope payload is {} bytes, expected {length}", payload.len());
+
+        // Read the authorization.
+        Self::from_bytes_le(payload)
+    }
+
+    /// Returns the envelope of the authorization in URL-safe base64, without padding.
+    pub fn to_base64(&self) -> Result<String> {
+        Ok(URL_SAFE_NO_PAD.encode(self.to_envelope()?))
+    }
+
+    /// Returns the authorization from the given base64 envelope.
+    pub fn from_base64(string: &str) -> Result<Self> {
+        Self::from_envelope(&URL_SAFE_NO_PAD.decode(string.trim())?)
+    }
+
+    /// Returns the envelope of the authorization as QR code payloads of at most the given number of characters each.
+    ///
+    /// Each part is `{index}/{total}:{data}`, where the data is uppercase base32, so that every part only uses
+    /// characters of the compact alphanumeric mode of a QR code.
+    pub fn to_qr_parts(&self, max_chars: usize) -> Result<Vec<String>> {
+        let data = encode_base32(&self.to_envelope()?);
+        // Reserve room for the largest prefix, i.e. `999/999:`.
+        ensure!(max_chars > 8, "Each QR part must fit more than 8 characters");
+        let chunks = data.as_bytes().chunks(max_chars - 8).collect::<Vec<_>>();
+        ensure!(chunks.len() <= 999, "The authorization needs more than 999 QR parts");
+        let total = chunks.len();
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| format!("{}/{total}:{}", index + 1, String::from_utf8_lossy(chunk)))
+            .collect())
+    }
+
+    /// Returns the authorization from the given QR code payloads, which may be in any order.
+    pub fn from_qr_parts(parts: &[impl AsRef<str>]) -> Result<Self> {
+        let mut chunks = vec![None; parts.len()];
+        for part in parts {
+            // Parse the prefix of the part.
+            let part = part.as_ref().trim();
+            let (prefix, chunk) = part.split_once(':').ok_or_else(|| anyhow!("Invalid QR part '{part}'"))?;
+            let (index, total) = prefix.split_once('/').ok_or_else(|| anyhow!("Invalid QR part '{part}'"))?;
+            let (index, total) = (index.parse::<usize>()?, total.parse::<usize>()?);
+            // Ensure the part belongs to this set of parts.
+            ensure!(total == parts.len(), "Expected {total} QR parts, found {}", parts.len());
+            ensure!((1..=total).contains(&index), "Invalid QR part index {index} of {total}");
+            if chunks[index - 1].replace(chunk).is_some() {
+                bail!("Duplicate QR part {index} of {total}");
+            }
+        }
+        // Decode the envelope.
+        let data = chunks.into_iter().flatten().collect::<String>();
+        Self::from_envelope(&decode_base32(&data)?)
+    }
+}
+
+/// Returns the checksum of the given bytes.
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE_IN_BYTES] {
+    let digest = Sha256::digest(bytes);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// Encodes the given bytes in base32, without padding.
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut string = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u16, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            string.push(QR_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        string.push(QR_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    string
+}
+
+/// Decodes the given base32 string, without padding.
+fn decode_base32(string: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(string.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for character in string.bytes() {
+        let value = match QR_ALPHABET.iter().position(|symbol| *symbol == character) {
+            Some(value) => value as u16,
+            None => bail!("Invalid base32 character '{}'", character as char),
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
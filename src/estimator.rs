@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{api, get_latest_height, API_URL};
+use snarkvm::prelude::{Deserialize, Serialize};
+
+use anyhow::Result;
+
+/// The default number of recent blocks to sample priority fees from.
+pub const DEFAULT_NUM_BLOCKS_TO_SAMPLE: u32 = 10;
+
+/// The priority fee level to target.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FeeLevel {
+    /// The 25th percentile of recently paid priority fees.
+    Low,
+    /// The 50th percentile of recently paid priority fees.
+    Medium,
+    /// The 90th percentile of recently paid priority fees.
+    High,
+}
+
+/// The suggested priority fees in microcredits.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// The low priority fee in microcredits.
+    pub low: u64,
+    /// The medium priority fee in microcredits.
+    pub medium: u64,
+    /// The high priority fee in microcredits.
+    pub high: u64,
+}
+
+impl FeeEstimate {
+    /// Initializes a new fee estimate from the given priority fees in microcredits.
+    pub fn from_priority_fees(mut priority_fees: Vec<u64>) -> Self {
+        // If there are no priority fees, then no priority fee is needed.
+        if priority_fees.is_empty() {
+            return Self::default();
+        }
+        // Sort the priority fees.
+        priority_fees.sort_unstable();
+        // Compute the percentiles using the nearest-rank method.
+        let percentile = |p: usize| priority_fees[(priority_fees.len() * p).div_ceil(100).saturating_sub(1)];
+        // Return the fee estimate.
+        Self { low: percentile(25), medium: percentile(50), high: percentile(90) }
+    }
+
+    /// Returns the priority fee in microcredits for the given fee level.
+    pub const fn get(&self, level: FeeLevel) -> u64 {
+        match level {
+            FeeLevel::Low => self.low,
+            FeeLevel::Medium => self.medium,
+            FeeLevel::High => self.high,
+        }
+    }
+}
+
+/// An estimator of the priority fee, from the priority fees paid in recent blocks.
+///
+/// The suggested priority fee may be applied when authorizing with `CreditsCall::authorize_with_fee_level`.
+pub struct FeeEstimator {
+    /// The API endpoint to sample blocks from.
+    endpoint: String,
+    /// The number of recent blocks to sample.
+    num_blocks: u32,
+}
+
+impl Default for FeeEstimator {
+    /// Initializes a new fee estimator for the default API endpoint.
+    fn default() -> Self {
+        Self::new(API_URL, DEFAULT_NUM_BLOCKS_TO_SAMPLE)
+    }
+}
+
+impl FeeEstimator {
+    /// Initializes a new fee estimator for the given API endpoint and number of recent blocks.
+    pub fn new(endpoint: &str, num_blocks: u32) -> Self {
+        Self { endpoint: endpoint.to_string(), num_blocks }
+    }
+
+    /// Returns the suggested priority fees, sampled from the priority fees paid in recent blocks.
+    pub fn estimate(&self) -> Result<FeeEstimate> {
+        // Retrieve the latest block height.
+        let latest_height = get_latest_height(&self.endpoint)?;
+        // Determine the starting block height.
+        let start_height = latest_height.saturating_sub(self.num_blocks.saturating_sub(1));
+
+        // Collect the priority fees paid by the included transactions.
+        let mut priority_fees = Vec::new();
+        for height in start_height..=latest_height {
+            // Retrieve the block.
+            let block = api::get_block(&self.endpoint, height)?;
+            // Retrieve the priority fees, skipping any transaction without a fee.
+            for confirmed in block.transactions().iter() {
+                if let Some(fee) = confirmed.transaction().fee_transition() {
+                    priority_fees.push(*fee.priority_amount()?);
+                }
+            }
+        }
+
+        // Return the fee estimate.
+        Ok(FeeEstimate::from_priority_fees(priority_fees))
+    }
+
+    /// Returns the suggested priority fee in microcredits for the given fee level.
+    ///
+    /// The result may be passed directly as the `priority_fee_in_microcredits` of any `Credits` function.
+    /// To apply it when authorizing, use `CreditsCall::authorize_with_fee_level` instead.
+    pub fn suggest(&self, level: FeeLevel) -> Result<u64> {
+        Ok(self.estimate()?.get(level))
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::Serialize;
+
+use anyhow::{anyhow, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The counter that keeps the temporary files of concurrent writes in the same process apart.
+static TEMPORARY_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Writes the given value as JSON to the given path, so that a crash never leaves a partially-written file.
+///
+/// The value is staged in a uniquely-named temporary file in the same directory, and flushed to disk
+/// before it atomically replaces the previous file.
+pub(crate) fn write_atomically(path: impl AsRef<Path>, value: &impl Serialize) -> Result<()> {
+    write_atomically_with(path.as_ref(), value, OpenOptions::new())
+}
+
+/// Writes the given value as JSON to the given path, like `write_atomically`,
+/// but only lets the owner read and write the file on unix.
+pub(crate) fn write_private_atomically(path: impl AsRef<Path>, value: &impl Serialize) -> Result<()> {
+    let mut options = OpenOptions::new();
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    write_atomically_with(path.as_ref(), value, options)
+}
+
+/// Writes the given value as JSON to the given path, creating the temporary file with the given options.
+fn write_atomically_with(path: &Path, value: &impl Serialize, mut options: OpenOptions) -> Result<()> {
+    // Name the temporary file after the full file name, the process, and a counter, so that it never collides.
+    let file_name = path.file_name().ok_or_else(|| anyhow!("The path '{}' is not a file", path.display()))?;
+    let temporary_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    // Stage the value in the temporary file, and flush it to disk.
+    let result = options
+        .write(true)
+        .create_new(true)
+        .open(&temporary_path)
+        .and_then(|mut file| file.write_all(serde_json::to_string_pretty(value)?.as_bytes()).and(file.sync_all()));
+    // Replace the previous file, or remove the temporary file if it could not be written.
+    match result.and_then(|_| fs::rename(&temporary_path, path)) {
+        Ok(()) => sync_parent(path),
+        Err(error) => {
+            let _ = fs::remove_file(&temporary_path);
+            Err(error.into())
+        }
+    }
+}
+
+/// Flushes the directory of the given path to disk on unix, so that a rename into it survives a power loss.
+fn sync_parent(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
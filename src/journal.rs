@@ -0,0 +1,182 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{file::write_atomically, Authorized, RetryPolicy, N};
+use snarkvm::{
+    ledger::block::Transaction,
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{bail, ensure, Result};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The status of a journaled authorization.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JournalStatus {
+    /// The authorization was recorded, but it is unknown whether it was submitted.
+    Pending,
+    /// The authorization was submitted, and resulted in the given transaction ID.
+    Submitted { transaction_id: String },
+    /// The transaction with the given ID was found on chain.
+    Confirmed { transaction_id: String },
+}
+
+/// A journaled authorization.
+///
+/// The transaction is found on chain by the transition IDs of the authorization, i.e. `Authorized::transition_ids`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// The authorization, so that it may be resubmitted without being re-signed.
+    pub authorization: Authorized<N>,
+    /// The status of the authorization.
+    pub status: JournalStatus,
+}
+
+/// A crash-safe journal of submitted authorizations, stored in a local file.
+///
+/// Each authorization is recorded before it is submitted, and marked with its transaction ID once it is returned.
+/// After a restart, `reconcile` checks each pending authorization against the chain before resubmitting it.
+/// As a resubmission reuses the same authorization, and the network rejects duplicate transition IDs,
+/// an authorization is never executed more than once.
+pub struct Journal {
+    /// The path to the journal file.
+    path: PathBuf,
+    /// The journal entries, keyed by execution ID.
+    entries: BTreeMap<String, JournalEntry>,
+}
+
+impl Journal {
+    /// Opens the journal at the given path, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match path.exists() {
+            true => serde_json::from_str(&fs::read_to_string(&path)?)?,
+            false => BTreeMap::new(),
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// Returns the journal entry for the given execution ID, if it exists.
+    pub fn get(&self, execution_id: &str) -> Option<&JournalEntry> {
+        self.entries.get(execution_id)
+    }
+
+    /// Returns the journal entries, keyed by execution ID.
+    pub fn entries(&self) -> impl '_ + Iterator<Item = (&String, &JournalEntry)> {
+        self.entries.iter()
+    }
+
+    /// Returns the journal entries that are not yet known to be submitted.
+    pub fn pending(&self) -> impl '_ + Iterator<Item = (&String, &JournalEntry)> {
+        self.entries.iter().filter(|(_, entry)| entry.status == JournalStatus::Pending)
+    }
+
+    /// Records the given authorization, returning its execution ID.
+    ///
+    /// If the authorization was already recorded, its existing entry is kept.
+    pub fn record(&mut self, authorization: &Authorized<N>) -> Result<String> {
+        // Compute the execution ID.
+        let execution_id = authorization.execution_id()?.to_string();
+        // Record the authorization, if it is new.
+        if !self.entries.contains_key(&execution_id) {
+            let entry = JournalEntry { authorization: authorization.clone(), status: JournalStatus::Pending };
+            self.entries.insert(execution_id.clone(), entry);
+            self.persist()?;
+        }
+        Ok(execution_id)
+    }
+
+    /// Records and executes the given authorization with the given retry policy, returning the resulting transaction.
+    ///
+    /// If the authorization was previously recorded, it must be reconciled with `reconcile` instead.
+    pub fn execute(&mut self, authorization: Authorized<N>, policy: &RetryPolicy) -> Result<Transaction<N>> {
+        // Compute the execution ID.
+        let execution_id = authorization.execution_id()?.to_string();
+        // Ensure the authorization has not been recorded before.
+        ensure!(
+            !self.entries.contains_key(&execution_id),
+            "The authorization '{execution_id}' was already recorded, reconcile the journal instead"
+        );
+        // Record the authorization before submitting it.
+        self.record(&authorization)?;
+        // Execute the authorization.
+        let transaction = authorization.execute_with(policy)?;
+        // Mark the transaction ID.
+        self.set_status(&execution_id, JournalStatus::Submitted { transaction_id: transaction.id().to_string() })?;
+        Ok(transaction)
+    }
+
+    /// Reconciles each entry that is not yet confirmed against the chain, at the endpoints of the given retry policy.
+    ///
+    /// An entry found on chain is marked as confirmed. A pending entry that is not found on chain
+    /// is resubmitted with the same authorization, and marked as submitted.
+    pub fn reconcile(&mut self, policy: &RetryPolicy) -> Result<()> {
+        let execution_ids = self.entries.keys().cloned().collect::<Vec<_>>();
+        for execution_id in execution_ids {
+            let entry = &self.entries[&execution_id];
+            // Skip entries that are already confirmed.
+            if matches!(entry.status, JournalStatus::Confirmed { .. }) {
+                continue;
+            }
+            // Find the transaction on chain.
+            let transaction_id = Self::find_transaction_id(&entry.authorization, policy)?;
+            match (transaction_id, &entry.status) {
+                // Mark the entry as confirmed.
+                (Some(transaction_id), _) => {
+                    self.set_status(&execution_id, JournalStatus::Confirmed { transaction_id })?;
+                }
+                // Resubmit the same authorization.
+                (None, JournalStatus::Pending) => {
+                    let transaction = entry.authorization.clone().execute_with(policy)?;
+                    let transaction_id = transaction.id().to_string();
+                    self.set_status(&execution_id, JournalStatus::Submitted { transaction_id })?;
+                }
+                // The submitted transaction is not yet confirmed.
+                (None, _) => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ID of the transaction that contains the given authorization, from the first endpoint that responds.
+    fn find_transaction_id(authorization: &Authorized<N>, policy: &RetryPolicy) -> Result<Option<String>> {
+        let mut errors = Vec::new();
+        for endpoint in policy.endpoints() {
+            match authorization.find_transaction_id(endpoint) {
+                Ok(transaction_id) => return Ok(transaction_id),
+                Err(error) => errors.push(format!("{endpoint}: {error}")),
+            }
+        }
+        bail!("The transaction could not be found on any endpoint:\n  {}", errors.join("\n  "))
+    }
+
+    /// Updates the status of the given entry, and persists the journal.
+    fn set_status(&mut self, execution_id: &str, status: JournalStatus) -> Result<()> {
+        match self.entries.get_mut(execution_id) {
+            Some(entry) => entry.status = status,
+            None => bail!("The journal entry '{execution_id}' does not exist"),
+        }
+        self.persist()
+    }
+
+    /// Persists the journal to its path.
+    fn persist(&self) -> Result<()> {
+        write_atomically(&self.path, &self.entries)
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{file::write_private_atomically, secret::parse_private_key, SecretString, N};
+use snarkvm::{
+    console::account::{Address, PrivateKey},
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305,
+    XNonce,
+};
+use core::str::FromStr;
+use rand::{CryptoRng, Rng};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+/// The version of the keystore file format.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// The key derivation function of a keystore entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "snake_case")]
+pub enum Kdf {
+    /// Argon2id, with the given memory cost in KiB, number of iterations, and degree of parallelism.
+    Argon2id { salt: String, memory_cost: u32, time_cost: u32, parallelism: u32 },
+}
+
+impl Kdf {
+    /// Samples a new Argon2id KDF with a random salt, and the recommended parameters of 19 MiB of memory and 2 iterations.
+    fn sample(rng: &mut (impl Rng + CryptoRng)) -> Self {
+        Self::Argon2id {
+            salt: hex::encode(rng.gen::<[u8; 16]>()),
+            memory_cost: 19 * 1024,
+            time_cost: 2,
+            parallelism: 1,
+        }
+    }
+
+    /// Derives the encryption key from the given password.
+    fn derive_key(&self, password: &str) -> Result<Zeroizing<[u8; 32]>> {
+        match self {
+            Self::Argon2id { salt, memory_cost, time_cost, parallelism } => {
+                let params = Params::new(*memory_cost, *time_cost, *parallelism, Some(32))
+                    .map_err(|error| anyhow!("Invalid Argon2id parameters: {error}"))?;
+                let mut key = Zeroizing::new([0u8; 32]);
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), &hex::decode(salt)?, key.as_mut())
+                    .map_err(|error| anyhow!("Failed to derive the keystore key: {error}"))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+/// The cipher of a keystore entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "snake_case")]
+pub enum Cipher {
+    /// XChaCha20-Poly1305, with the given nonce.
+    Xchacha20poly1305 { nonce: String },
+}
+
+/// An encrypted private key, as stored in a versioned JSON keystore file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    /// The version of the keystore file format.
+    version: u32,
+    /// The address of the private key.
+    address: String,
+    /// The key derivation function.
+    kdf: Kdf,
+    /// The cipher.
+    cipher: Cipher,
+    /// The encrypted private key, in hex.
+    ciphertext: String,
+}
+
+impl KeystoreEntry {
+    /// Encrypts the given private key with the given password.
+    pub fn encrypt(private_key: &PrivateKey<N>, password: &str, rng: &mut (impl Rng + CryptoRng)) -> Result<Self> {
+        // Derive the encryption key.
+        let kdf = Kdf::sample(rng);
+        let key = kdf.derive_key(password)?;
+        // Encrypt the private key, authenticating the version and address.
+        let address = Address::try_from(private_key)?.to_string();
+        let nonce = rng.gen::<[u8; 24]>();
+        let plaintext = Zeroizing::new(private_key.to_string());
+        let ciphertext = XChaCha20Poly1305::new(key.as_ref().into())
+            .encrypt(XNonce::from_slice(&nonce), Payload {
+                msg: plaintext.as_bytes(),
+                aad: &Self::associated_data(KEYSTORE_VERSION, &address),
+            })
+            .map_err(|_| anyhow!("Failed to encrypt the private key"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address,
+            kdf,
+            cipher: Cipher::Xchacha20poly1305 { nonce: hex::encode(nonce) },
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the private key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<PrivateKey<N>>> {
+        ensure!(self.version == KEYSTORE_VERSION, "Unsupported keystore version {}", self.version);
+
+        // Derive the encryption key.
+        let key = self.kdf.derive_key(password)?;
+        // Decrypt the private key.
+        let plaintext = match &self.cipher {
+            Cipher::Xchacha20poly1305 { nonce } => {
+                let nonce = hex::decode(nonce)?;
+                ensure!(nonce.len() == 24, "The keystore nonce must be 24 bytes");
+                Zeroizing::new(
+                    XChaCha20Poly1305::new(key.as_ref().into())
+                        .decrypt(XNonce::from_slice(&nonce), Payload {
+                            msg: &hex::decode(&self.ciphertext)?,
+                            aad: &Self::associated_data(self.version, &self.address),
+                        })
+                        .map_err(|_| anyhow!("Incorrect password for '{}'", self.address))?,
+                )
+            }
+        };
+        // Parse the private key.
+        let private_key = parse_private_key(core::str::from_utf8(&plaintext)?)?;
+        // Ensure the private key matches the address.
+        ensure!(
+            Address::try_from(&*private_key)?.to_string() == self.address,
+            "The keystore entry does not match its address '{}'",
+            self.address
+        );
+        Ok(private_key)
+    }
+
+    /// Returns the address of the private key.
+    pub fn address(&self) -> Result<Address<N>> {
+        Address::from_str(&self.address)
+    }
+
+    /// Returns the data that is authenticated alongside the private key.
+    fn associated_data(version: u32, address: &str) -> Vec<u8> {
+        format!("credits-keystore-v{version}:{address}").into_bytes()
+    }
+}
+
+/// A directory of encrypted private keys, with one `{address}.json` file per key.
+pub struct Keystore {
+    /// The path to the keystore directory.
+    directory: PathBuf,
+}
+
+impl Keystore {
+    /// Opens the keystore at the given directory, creating it if it does not exist.
+    pub fn open(directory: impl AsRef<Path>) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    /// Encrypts and stores the given private key with the given password, returning its address.
+    pub fn import(&self, private_key: &str, password: &str, rng: &mut (impl Rng + CryptoRng)) -> Result<Address<N>> {
+        let private_key = parse_private_key(private_key)?;
+        self.import_entry(KeystoreEntry::encrypt(&private_key, password, rng)?)
+    }
+
+    /// Stores the given encrypted keystore entry, i.e. one that was exported from another keystore, returning its address.
+    pub fn import_entry(&self, entry: KeystoreEntry) -> Result<Address<N>> {
+        // Ensure the entry does not already exist.
+        let address = entry.address()?;
+        let path = self.path(&address);
+        if path.exists() {
+            bail!("The keystore already contains '{address}'");
+        }
+        // Store the entry, so that only the owner can read it.
+        write_private_atomically(path, &entry)?;
+        Ok(address)
+    }
+
+    /// Returns the encrypted keystore entry for the given address, i.e. to back it up or import it into another keystore.
+    pub fn export(&self, address: &Address<N>) -> Result<KeystoreEntry> {
+        let path = self.path(address);
+        ensure!(path.exists(), "The keystore does not contain '{address}'");
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Returns the addresses in the keystore, in sorted order.
+    pub fn list(&self) -> Result<Vec<Address<N>>> {
+        let mut addresses = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                addresses.push(serde_json::from_str::<KeystoreEntry>(&fs::read_to_string(path)?)?.address()?);
+            }
+        }
+        addresses.sort_by_key(|address| address.to_string());
+        Ok(addresses)
+    }
+
+    /// Decrypts the private key for the given address, returning it as a secret string for use with `Credits`.
+    pub fn unlock(&self, address: &Address<N>, password: &str) -> Result<SecretString> {
+        Ok(SecretString::new(self.export(address)?.decrypt(password)?.to_string()))
+    }
+
+    /// Returns the path of the keystore file for the given address.
+    fn path(&self, address: &Address<N>) -> PathBuf {
+        self.directory.join(format!("{address}.json"))
+    }
+}
@@ -15,12 +15,89 @@
 #[macro_use]
 extern crate lazy_static;
 
+mod abi;
+pub use abi::*;
+
+mod account;
+pub use account::*;
+
+mod api;
+
 mod authorized;
 pub use authorized::*;
 
+mod batch;
+pub use batch::*;
+
+mod call;
+pub use call::*;
+
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "cli")]
+pub use cli::*;
+
 mod config;
 pub use config::*;
 
+mod derivation;
+pub use derivation::*;
+
+mod envelope;
+pub use envelope::*;
+
+mod estimator;
+pub use estimator::*;
+
+mod file;
+
+mod journal;
+pub use journal::*;
+
+mod keystore;
+pub use keystore::*;
+
+mod microcredits;
+pub use microcredits::*;
+
+mod mnemonic;
+pub use mnemonic::*;
+
+mod mode;
+pub use mode::*;
+
+mod parallel;
+
+mod policy;
+pub use policy::*;
+
+mod query;
+pub use query::*;
+
+mod receipt;
+pub use receipt::*;
+
+mod request;
+pub use request::*;
+
+mod retry;
+pub use retry::*;
+
+mod scanner;
+pub use scanner::*;
+
+mod secret;
+pub use secret::*;
+
+mod staking;
+pub use staking::*;
+
+mod store;
+pub use store::*;
+
+mod watcher;
+pub use watcher::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -30,7 +107,9 @@ use snarkvm::console::{
     types::{Boolean, U64},
 };
 
-use anyhow::Result;
+use secret::{address_of, parse_private_key};
+
+use anyhow::{ensure, Result};
 use core::str::FromStr;
 use rand::{CryptoRng, Rng};
 
@@ -41,62 +120,86 @@ impl Credits {
     pub fn bond_public(
         private_key: &str,
         validator: &str,
-        amount_in_microcredits: u64,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        amount_in_microcredits: impl Into<Microcredits>,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the validator's address.
         let validator = Address::<N>::from_str(validator)?;
         // Initialize the amount in microcredits.
-        let amount_in_microcredits = U64::<N>::new(amount_in_microcredits);
+        let amount_in_microcredits = amount_in_microcredits.into();
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "bond_public");
         // Construct the inputs.
-        let inputs =
-            vec![Value::<N>::from(Literal::Address(validator)), Value::from(Literal::U64(amount_in_microcredits))];
+        let inputs = vec![
+            Value::<N>::from(Literal::Address(validator)),
+            Value::from(Literal::U64(U64::new(*amount_in_microcredits))),
+        ];
+
+        // Ensure the total cost does not overflow.
+        get_total_cost_in_microcredits(
+            program_id,
+            function_name,
+            amount_in_microcredits,
+            priority_fee_in_microcredits,
+        )?;
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that any staker to unbond their microcredits from a validator.
     pub fn unbond_public(
         private_key: &str,
-        amount_in_microcredits: u64,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        amount_in_microcredits: impl Into<Microcredits>,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the amount in microcredits.
-        let amount_in_microcredits = U64::<N>::new(amount_in_microcredits);
+        let amount_in_microcredits = amount_in_microcredits.into();
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "unbond_public");
         // Construct the inputs.
-        let inputs = vec![Value::from(Literal::U64(amount_in_microcredits))];
+        let inputs = vec![Value::from(Literal::U64(U64::new(*amount_in_microcredits)))];
+
+        // Ensure the total cost does not overflow.
+        get_total_cost_in_microcredits(
+            program_id,
+            function_name,
+            amount_in_microcredits,
+            priority_fee_in_microcredits,
+        )?;
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that allows a validator to unbond any delegator that is bonded to them.
     pub fn unbond_delegator_as_validator(
         private_key: &str,
         delegator: &str,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the delegator's address.
         let delegator = Address::<N>::from_str(delegator)?;
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "unbond_delegator_as_validator");
@@ -104,38 +207,42 @@ impl Credits {
         let inputs = vec![Value::<N>::from(Literal::Address(delegator))];
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that allows any staker to claim their microcredits after the unbonding period.
     pub fn claim_unbond_public(
         private_key: &str,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "claim_unbond_public");
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, vec![], priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, vec![], priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that allows a validator to set their state to be either opened or closed to stakers.
     pub fn set_validator_state(
         private_key: &str,
         is_open: bool,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the 'is_open' boolean flag.
         let is_open = Boolean::<N>::new(is_open);
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "set_validator_state");
@@ -143,71 +250,197 @@ impl Credits {
         let inputs = vec![Value::<N>::from(Literal::Boolean(is_open))];
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that transfers public credits from the sender to the recipient.
     pub fn transfer_public(
         private_key: &str,
         recipient: &str,
-        amount_in_microcredits: u64,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        amount_in_microcredits: impl Into<Microcredits>,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the recipient.
         let recipient = Address::<N>::from_str(recipient)?;
         // Initialize the amount in microcredits.
-        let amount_in_microcredits = U64::<N>::new(amount_in_microcredits);
+        let amount_in_microcredits = amount_in_microcredits.into();
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "transfer_public");
         // Construct the inputs.
-        let inputs =
-            vec![Value::<N>::from(Literal::Address(recipient)), Value::from(Literal::U64(amount_in_microcredits))];
+        let inputs = vec![
+            Value::<N>::from(Literal::Address(recipient)),
+            Value::from(Literal::U64(U64::new(*amount_in_microcredits))),
+        ];
+
+        // Ensure the total cost does not overflow.
+        get_total_cost_in_microcredits(
+            program_id,
+            function_name,
+            amount_in_microcredits,
+            priority_fee_in_microcredits,
+        )?;
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
     }
 
     /// Returns a transaction that transfers public to private credits from the sender to the recipient.
     pub fn transfer_public_to_private(
         private_key: &str,
         recipient: &str,
-        amount_in_microcredits: u64,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        amount_in_microcredits: impl Into<Microcredits>,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Initialize the private key.
-        let private_key = PrivateKey::<N>::from_str(private_key)?;
+        let private_key = parse_private_key(private_key)?;
         // Initialize the recipient.
         let recipient = Address::<N>::from_str(recipient)?;
         // Initialize the amount in microcredits.
-        let amount_in_microcredits = U64::<N>::new(amount_in_microcredits);
+        let amount_in_microcredits = amount_in_microcredits.into();
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
 
         // Construct the program ID and function name.
         let (program_id, function_name) = ("credits.aleo", "transfer_public_to_private");
         // Construct the inputs.
-        let inputs =
-            vec![Value::<N>::from(Literal::Address(recipient)), Value::from(Literal::U64(amount_in_microcredits))];
+        let inputs = vec![
+            Value::<N>::from(Literal::Address(recipient)),
+            Value::from(Literal::U64(U64::new(*amount_in_microcredits))),
+        ];
+
+        // Ensure the total cost does not overflow.
+        get_total_cost_in_microcredits(
+            program_id,
+            function_name,
+            amount_in_microcredits,
+            priority_fee_in_microcredits,
+        )?;
+
+        // Construct the authorization.
+        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, mode, rng)
+    }
+
+    /// Returns a transaction that transfers the sender's entire public balance, net of fees, to the recipient,
+    /// reading the balance from the given API endpoint.
+    pub fn sweep_public(
+        endpoint: &str,
+        private_key: &str,
+        recipient: &str,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<Authorized<N>> {
+        // Derive the sender's address.
+        let sender = address_of(private_key)?;
+        // Initialize the priority fee in microcredits.
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
+
+        // Retrieve the sender's public balance.
+        let balance_in_microcredits = get_public_balance(endpoint, &sender.to_string())?;
+        // Compute the amount that remains after fees.
+        let amount_in_microcredits = Self::sweep_amount(balance_in_microcredits, priority_fee_in_microcredits)?;
 
         // Construct the authorization.
-        Self::authorize(&private_key, program_id, function_name, inputs, priority_fee_in_microcredits, broadcast, rng)
+        Self::transfer_public(private_key, recipient, amount_in_microcredits, priority_fee_in_microcredits, mode, rng)
     }
 }
 
 impl Credits {
-    /// An internal method that authorizes a function call with a corresponding fee.
+    /// An internal method that computes the amount to sweep from the given balance, net of the `transfer_public` fees.
+    fn sweep_amount(
+        balance_in_microcredits: Microcredits,
+        priority_fee_in_microcredits: Microcredits,
+    ) -> Result<Microcredits> {
+        // Ensure the balance is not zero.
+        ensure!(!balance_in_microcredits.is_zero(), "The public balance is zero, there is nothing to sweep");
+        // Compute the total fee.
+        let fee_in_microcredits = get_total_cost_in_microcredits(
+            "credits.aleo",
+            "transfer_public",
+            Microcredits::ZERO,
+            priority_fee_in_microcredits,
+        )?;
+        // Ensure the balance exceeds the total fee.
+        ensure!(
+            balance_in_microcredits > fee_in_microcredits,
+            "The public balance of {balance_in_microcredits} is insufficient to cover the fee of {fee_in_microcredits}"
+        );
+        // Return the remaining amount.
+        balance_in_microcredits.checked_sub(fee_in_microcredits)
+    }
+
+    /// An internal method that authorizes a function call with a corresponding fee,
+    /// once it is checked against the installed spending policy, if any.
+    ///
+    /// The private key is only borrowed, so the caller's zeroizing copy is the only copy held by this crate.
     fn authorize(
         private_key: &PrivateKey<N>,
         program_id: &str,
         function_name: &str,
         inputs: Vec<Value<N>>,
-        priority_fee_in_microcredits: u64,
-        broadcast: bool,
+        priority_fee_in_microcredits: Microcredits,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<Authorized<N>> {
+        // Retrieve the installed spending policy.
+        let policy = match SpendingPolicy::installed()? {
+            Some(policy) => policy,
+            None => {
+                return Self::authorize_with_fee(
+                    private_key,
+                    program_id,
+                    function_name,
+                    inputs,
+                    priority_fee_in_microcredits,
+                    mode,
+                    rng,
+                );
+            }
+        };
+
+        // Check the spending before anything is signed, and record it unless it is a dry run, which is never executed.
+        let spend = Spend::new(private_key, function_name, &inputs, priority_fee_in_microcredits)?;
+        let charge = match mode.is_dry_run() {
+            true => {
+                policy.check(&spend)?;
+                None
+            }
+            false => Some(policy.enforce(&spend)?),
+        };
+        // Authorize the call.
+        let result = Self::authorize_with_fee(
+            private_key,
+            program_id,
+            function_name,
+            inputs,
+            priority_fee_in_microcredits,
+            mode,
+            rng,
+        );
+        // Refund the spending if the authorization failed, keeping its error over any error of the refund.
+        if let (Err(_), Some(charge)) = (&result, charge) {
+            let _ = policy.refund(&spend, charge);
+        }
+        result
+    }
+
+    /// An internal method that authorizes a function call with a corresponding fee.
+    fn authorize_with_fee(
+        private_key: &PrivateKey<N>,
+        program_id: &str,
+        function_name: &str,
+        inputs: Vec<Value<N>>,
+        priority_fee_in_microcredits: Microcredits,
+        mode: ExecutionMode,
         rng: &mut (impl Rng + CryptoRng),
     ) -> Result<Authorized<N>> {
         // Authorize the main function.
@@ -215,19 +448,21 @@ impl Credits {
         // Retrieve the execution ID.
         let execution_id = function.to_execution_id()?;
         // Determine the base fee in microcredits.
-        let base_fee_in_microcredits = get_base_fee_in_microcredits(program_id, function_name)?;
+        let base_fee_in_microcredits = Microcredits::new(get_base_fee_in_microcredits(program_id, function_name)?);
+        // Ensure the fee does not overflow.
+        base_fee_in_microcredits.checked_add(priority_fee_in_microcredits)?;
         // Authorize the fee.
-        let fee = match base_fee_in_microcredits == 0 && priority_fee_in_microcredits == 0 {
+        let fee = match base_fee_in_microcredits.is_zero() && priority_fee_in_microcredits.is_zero() {
             true => None,
             false => Some(PROCESS.authorize_fee_public::<A, _>(
                 private_key,
-                base_fee_in_microcredits,
-                priority_fee_in_microcredits,
+                *base_fee_in_microcredits,
+                *priority_fee_in_microcredits,
                 execution_id,
                 rng,
             )?),
         };
         // Construct the authorization.
-        Ok(Authorized::<N>::new(function, fee, broadcast))
+        Ok(Authorized::<N>::new(function, fee, mode))
     }
 }
@@ -0,0 +1,35 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use credits::{Cli, OutputFormat};
+
+use clap::Parser;
+
+fn main() {
+    // Parse the command.
+    let cli = Cli::parse();
+    let output = cli.output();
+
+    // Run the command.
+    match cli.run() {
+        Ok(result) => println!("{result}"),
+        Err(error) => {
+            match output {
+                OutputFormat::Text => eprintln!("Error: {error}"),
+                OutputFormat::Json => eprintln!("{}", serde_json::json!({ "error": error.to_string() })),
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{Deserialize, Serialize};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use core::{fmt, ops::Deref, str::FromStr};
+
+/// The number of microcredits in one credit.
+pub const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+
+/// The number of decimal places in one credit.
+const CREDITS_DECIMALS: usize = 6;
+
+/// The unit to display an amount in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Displays the amount in credits, i.e. `1.5 credits`.
+    Credits,
+    /// Displays the amount in microcredits, i.e. `1500000 microcredits`.
+    Microcredits,
+}
+
+/// An amount in microcredits, with overflow-checked arithmetic.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Microcredits(u64);
+
+impl Microcredits {
+    /// The zero amount.
+    pub const ZERO: Self = Self(0);
+
+    /// Initializes a new amount from the given number of microcredits.
+    pub const fn new(microcredits: u64) -> Self {
+        Self(microcredits)
+    }
+
+    /// Initializes a new amount from the given number of whole credits.
+    pub fn from_credits(credits: u64) -> Result<Self> {
+        match credits.checked_mul(MICROCREDITS_PER_CREDIT) {
+            Some(microcredits) => Ok(Self(microcredits)),
+            None => bail!("The amount of {credits} credits overflows the number of microcredits"),
+        }
+    }
+
+    /// Returns the amount in microcredits.
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the amount is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the sum of the two amounts, or an error if the sum overflows.
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        match self.0.checked_add(other.0) {
+            Some(microcredits) => Ok(Self(microcredits)),
+            None => bail!("The sum of {self} and {other} overflows"),
+        }
+    }
+
+    /// Returns the difference of the two amounts, or an error if the difference underflows.
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(microcredits) => Ok(Self(microcredits)),
+            None => bail!("The difference of {self} and {other} underflows"),
+        }
+    }
+
+    /// Returns the amount multiplied by the given factor, or an error if the product overflows.
+    pub fn checked_mul(self, factor: u64) -> Result<Self> {
+        match self.0.checked_mul(factor) {
+            Some(microcredits) => Ok(Self(microcredits)),
+            None => bail!("The product of {self} and {factor} overflows"),
+        }
+    }
+
+    /// Returns the sum of the given amounts, or an error if the sum overflows.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Self>) -> Result<Self> {
+        amounts.into_iter().try_fold(Self::ZERO, Self::checked_add)
+    }
+
+    /// Returns the amount as a string in the given unit.
+    pub fn to_string_in(&self, unit: Unit) -> String {
+        match unit {
+            Unit::Credits => {
+                // Split the amount into whole credits and the fractional microcredits.
+                let (credits, fraction) = (self.0 / MICROCREDITS_PER_CREDIT, self.0 % MICROCREDITS_PER_CREDIT);
+                match fraction {
+                    0 => format!("{credits} credits"),
+                    _ => {
+                        // Format the fraction without any trailing zeros.
+                        let fraction = format!("{fraction:0CREDITS_DECIMALS$}");
+                        format!("{credits}.{} credits", fraction.trim_end_matches('0'))
+                    }
+                }
+            }
+            Unit::Microcredits => format!("{} microcredits", self.0),
+        }
+    }
+}
+
+impl From<u64> for Microcredits {
+    /// Initializes a new amount from the given number of microcredits.
+    fn from(microcredits: u64) -> Self {
+        Self(microcredits)
+    }
+}
+
+impl From<Microcredits> for u64 {
+    /// Returns the amount in microcredits.
+    fn from(amount: Microcredits) -> Self {
+        amount.0
+    }
+}
+
+impl Deref for Microcredits {
+    type Target = u64;
+
+    /// Returns the amount in microcredits.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for Microcredits {
+    type Err = anyhow::Error;
+
+    /// Parses an amount such as `1.5 credits`, `1500000 microcredits`, or `1_500_000`.
+    ///
+    /// A number without a unit is interpreted as microcredits, and must not contain a decimal point.
+    fn from_str(string: &str) -> Result<Self> {
+        // Split the number from the unit.
+        let string = string.trim();
+        let (number, unit) = match string.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(index) => (string[..index].trim(), Some(string[index..].trim())),
+            None => (string, None),
+        };
+        // Remove any digit separators from the number.
+        let number = number.replace('_', "");
+        ensure!(!number.is_empty(), "Missing the number in amount '{string}'");
+
+        match unit {
+            Some("credit" | "credits") => {
+                // Split the number into whole credits and the fractional credits.
+                let (credits, fraction) = number.split_once('.').unwrap_or((&number, ""));
+                ensure!(!credits.is_empty() || !fraction.is_empty(), "Missing the number in amount '{string}'");
+                ensure!(
+                    fraction.len() <= CREDITS_DECIMALS,
+                    "The amount '{string}' has more than {CREDITS_DECIMALS} decimal places"
+                );
+                // Parse the whole credits.
+                let credits = match credits.is_empty() {
+                    true => Self::ZERO,
+                    false => Self::from_credits(parse_digits(credits, string)?)?,
+                };
+                // Parse the fractional credits as microcredits.
+                let fraction = match fraction.is_empty() {
+                    true => Self::ZERO,
+                    false => Self(parse_digits(&format!("{fraction:0<CREDITS_DECIMALS$}"), string)?),
+                };
+                credits.checked_add(fraction)
+            }
+            Some("microcredit" | "microcredits") | None => {
+                ensure!(
+                    !number.contains('.'),
+                    "The amount '{string}' has a decimal point, specify the unit as 'credits' to use decimals"
+                );
+                Ok(Self(parse_digits(&number, string)?))
+            }
+            Some(unit) => bail!("Unknown unit '{unit}' in amount '{string}'"),
+        }
+    }
+}
+
+impl fmt::Display for Microcredits {
+    /// Displays the amount in microcredits, or in credits with the alternate flag (`{:#}`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.alternate() {
+            true => write!(f, "{}", self.to_string_in(Unit::Credits)),
+            false => write!(f, "{}", self.to_string_in(Unit::Microcredits)),
+        }
+    }
+}
+
+/// Parses the given string of ASCII digits into a `u64`.
+fn parse_digits(digits: &str, amount: &str) -> Result<u64> {
+    ensure!(digits.bytes().all(|b| b.is_ascii_digit()), "Invalid number in amount '{amount}'");
+    digits.parse().map_err(|_| anyhow!("The amount '{amount}' overflows the number of microcredits"))
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{MasterSeed, SecretString};
+
+use anyhow::{anyhow, ensure, Result};
+use core::fmt;
+use rand::{CryptoRng, Rng};
+use zeroize::Zeroizing;
+
+/// A BIP39 mnemonic phrase in English, from which accounts are restored by index.
+///
+/// The phrase and optional passphrase are stretched into the 64-byte BIP39 seed, which is used as the `MasterSeed`
+/// of the account derivation. As snarkVM does not document a standard mnemonic derivation, accounts restored
+/// from a phrase are only compatible with wallets that use this same derivation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic(SecretString);
+
+impl Mnemonic {
+    /// The supported number of words in a phrase.
+    pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+    /// Samples a new random phrase with the given number of words.
+    pub fn new(word_count: usize, rng: &mut (impl Rng + CryptoRng)) -> Result<Self> {
+        ensure!(Self::WORD_COUNTS.contains(&word_count), "The phrase must have 12, 15, 18, 21, or 24 words");
+        // Sample 32 bits of entropy for every 3 words.
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(entropy.as_mut());
+        let mnemonic = bip39::Mnemonic::from_entropy(&entropy[..word_count / 3 * 4])
+            .map_err(|error| anyhow!("Failed to generate the phrase: {error}"))?;
+        Ok(Self(SecretString::new(mnemonic.to_string())))
+    }
+
+    /// Restores the given phrase, ensuring every word is valid and the checksum matches.
+    pub fn from_phrase(phrase: &str) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map_err(|error| anyhow!("Invalid mnemonic phrase: {error}"))?;
+        Ok(Self(SecretString::new(mnemonic.to_string())))
+    }
+
+    /// Returns the normalized phrase, i.e. to back it up.
+    pub fn phrase(&self) -> &str {
+        self.0.expose()
+    }
+
+    /// Returns the number of words in the phrase.
+    pub fn word_count(&self) -> usize {
+        self.phrase().split(' ').count()
+    }
+
+    /// Returns the master seed of the phrase, stretched with the given passphrase, which may be empty.
+    pub fn to_master_seed(&self, passphrase: &str) -> Result<MasterSeed> {
+        let mnemonic = bip39::Mnemonic::parse_in_normalized(bip39::Language::English, self.phrase())
+            .map_err(|error| anyhow!("Invalid mnemonic phrase: {error}"))?;
+        MasterSeed::from_bytes(Zeroizing::new(mnemonic.to_seed(passphrase)).as_slice())
+    }
+
+    /// Returns the private key at the given index, for use with `Credits`.
+    pub fn private_key(&self, passphrase: &str, index: u32) -> Result<SecretString> {
+        self.to_master_seed(passphrase)?.private_key(index)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    /// Redacts the phrase.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic(***)")
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{Deserialize, Serialize};
+
+/// How an authorization is executed.
+///
+/// The execution API receives the mode as the `broadcast` flag of the authorization, which is `true` only for
+/// `Broadcast`. A `DryRun` authorization is never sent to the execution API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// The server proves the transaction and returns it, without broadcasting it to the network.
+    Prove,
+    /// The server proves the transaction, broadcasts it to the network, and returns it.
+    Broadcast,
+    /// The authorization is only inspected locally, i.e. for its IDs and fees, and `execute` fails.
+    DryRun,
+}
+
+impl ExecutionMode {
+    /// Returns `true` if the server broadcasts the transaction.
+    pub const fn is_broadcast(&self) -> bool {
+        matches!(self, Self::Broadcast)
+    }
+
+    /// Returns `true` if the authorization is never sent to the server.
+    pub const fn is_dry_run(&self) -> bool {
+        matches!(self, Self::DryRun)
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Authorized, Credits, CreditsCall, ExecutionMode, Microcredits, N};
+
+use anyhow::Result;
+use rand::{rngs::StdRng, CryptoRng, Rng, SeedableRng};
+use rayon::prelude::*;
+
+impl Credits {
+    /// Returns the authorizations for the given calls, authorized concurrently on the current rayon thread pool.
+    ///
+    /// Every call is authorized by the same private key, and the results are returned in the order of the calls.
+    /// To bound the number of threads, run this method inside `rayon::ThreadPool::install`.
+    pub fn authorize_many(
+        private_key: &str,
+        calls: &[CreditsCall],
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        mode: ExecutionMode,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Vec<Result<Authorized<N>>> {
+        let priority_fee_in_microcredits = priority_fee_in_microcredits.into();
+        par_authorize(calls, rng, |_, call, rng| call.authorize(private_key, priority_fee_in_microcredits, mode, rng))
+    }
+}
+
+/// Authorizes each of the given items concurrently, with a separate RNG for each item.
+///
+/// The RNG of each item is seeded from the given RNG, so the authorizations are reproducible for a given seed,
+/// regardless of how the items are scheduled across threads.
+pub(crate) fn par_authorize<T: Sync>(
+    items: &[T],
+    rng: &mut (impl Rng + CryptoRng),
+    authorize: impl Fn(usize, &T, &mut StdRng) -> Result<Authorized<N>> + Sync,
+) -> Vec<Result<Authorized<N>>> {
+    // Sample a seed for each item.
+    let seeds = items.iter().map(|_| rng.gen()).collect::<Vec<[u8; 32]>>();
+
+    // Authorize the items in parallel.
+    items
+        .par_iter()
+        .zip_eq(seeds)
+        .enumerate()
+        .map(|(index, (item, seed))| authorize(index, item, &mut StdRng::from_seed(seed)))
+        .collect()
+}
@@ -0,0 +1,410 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{file::write_atomically, get_total_cost_in_microcredits, Microcredits, N};
+use snarkvm::{
+    console::{
+        account::{Address, PrivateKey},
+        program::{Literal, Plaintext, Value},
+    },
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{anyhow, Result};
+use core::{fmt, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+lazy_static! {
+    /// The spending policy that every authorization is checked against, if one is installed.
+    static ref POLICY: RwLock<Option<Arc<SpendingPolicy>>> = RwLock::new(None);
+}
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// A violation of the spending policy.
+///
+/// The violation is returned inside the `anyhow::Error` of the `Credits` function, and may be recovered with
+/// `error.downcast_ref::<PolicyViolation>()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The recipient is on the denylist.
+    RecipientDenied { recipient: String },
+    /// The recipient is not on the allowlist.
+    RecipientNotAllowed { recipient: String },
+    /// The priority fee exceeds the maximum priority fee.
+    PriorityFeeTooHigh { priority_fee: Microcredits, max_priority_fee: Microcredits },
+    /// The amount reaches the confirmation threshold, and was not approved.
+    ConfirmationRequired { amount: Microcredits, threshold: Microcredits },
+    /// The total cost would exceed the daily limit of the sender.
+    DailyLimitExceeded { sender: String, limit: Microcredits, spent: Microcredits, requested: Microcredits },
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RecipientDenied { recipient } => write!(f, "The recipient '{recipient}' is denied by the spending policy"),
+            Self::RecipientNotAllowed { recipient } => {
+                write!(f, "The recipient '{recipient}' is not allowed by the spending policy")
+            }
+            Self::PriorityFeeTooHigh { priority_fee, max_priority_fee } => {
+                write!(f, "The priority fee of {priority_fee} exceeds the maximum of {max_priority_fee}")
+            }
+            Self::ConfirmationRequired { amount, threshold } => {
+                write!(f, "The amount of {amount} requires an approval, as it reaches the threshold of {threshold}")
+            }
+            Self::DailyLimitExceeded { sender, limit, spent, requested } => write!(
+                f,
+                "The cost of {requested} exceeds the daily limit of {limit} for '{sender}', which has spent {spent} today"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+/// The spending of an authorization, as checked by the spending policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Spend {
+    /// The address of the sender.
+    pub sender: String,
+    /// The function name.
+    pub function_name: String,
+    /// The recipient of the amount, if the function moves credits to an address.
+    pub recipient: Option<String>,
+    /// The amount moved to the recipient in microcredits.
+    pub amount: Microcredits,
+    /// The priority fee in microcredits.
+    pub priority_fee: Microcredits,
+}
+
+impl Spend {
+    /// Returns the spending of a call to the given `credits.aleo` function with the given inputs.
+    ///
+    /// Only `bond_public`, `transfer_public`, and `transfer_public_to_private` move credits to an address,
+    /// so every other function only spends its fee.
+    pub(crate) fn new(
+        private_key: &PrivateKey<N>,
+        function_name: &str,
+        inputs: &[Value<N>],
+        priority_fee_in_microcredits: Microcredits,
+    ) -> Result<Self> {
+        let (recipient, amount) = match function_name {
+            "bond_public" | "transfer_public" | "transfer_public_to_private" => match inputs {
+                [Value::Plaintext(Plaintext::Literal(Literal::Address(recipient), _)), Value::Plaintext(Plaintext::Literal(Literal::U64(amount), _))] => {
+                    (Some(recipient.to_string()), Microcredits::new(**amount))
+                }
+                _ => return Err(anyhow!("Invalid inputs for '{function_name}'")),
+            },
+            _ => (None, Microcredits::ZERO),
+        };
+        Ok(Self {
+            sender: Address::try_from(private_key)?.to_string(),
+            function_name: function_name.to_string(),
+            recipient,
+            amount,
+            priority_fee: priority_fee_in_microcredits,
+        })
+    }
+
+    /// Returns the total cost of the spending, including the amount and fees.
+    pub fn total(&self) -> Result<Microcredits> {
+        get_total_cost_in_microcredits("credits.aleo", &self.function_name, self.amount, self.priority_fee)
+    }
+}
+
+/// The spending of a sender on a given day.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DailySpending {
+    /// The day, in days since the Unix epoch.
+    day: u64,
+    /// The total cost authorized on the day.
+    spent: Microcredits,
+}
+
+/// A spending recorded towards the daily limit of its sender, which is refunded if its authorization fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Charge {
+    /// The day the spending was recorded on, in days since the Unix epoch.
+    day: u64,
+    /// The recorded total cost.
+    total: Microcredits,
+}
+
+/// An approval of a spending that reaches the confirmation threshold, which is consumed by one authorization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Approval {
+    /// The address of the sender.
+    sender: String,
+    /// The address of the recipient.
+    recipient: String,
+    /// The approved amount.
+    amount: Microcredits,
+}
+
+/// A spending policy, which every `Credits` function checks before it signs, once the policy is installed.
+///
+/// The policy is loaded from a JSON file, in which every rule is optional:
+///
+/// ```json
+/// {
+///     "daily_limit": 100000000,
+///     "daily_limits": { "aleo1...": 500000000 },
+///     "allowlist": ["aleo1..."],
+///     "denylist": ["aleo1..."],
+///     "max_priority_fee": 1000000,
+///     "confirmation_threshold": 50000000
+/// }
+/// ```
+///
+/// The amounts are in microcredits, and the daily limits cover the total cost of the authorizations of a sender
+/// in each UTC day, including fees. The recipient lists and the confirmation threshold only apply to the functions
+/// that move credits to an address, i.e. `bond_public`, `transfer_public`, and `transfer_public_to_private`.
+/// A dry run is checked against the policy, but is never recorded and never consumes an approval.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpendingPolicy {
+    /// The daily limit of every sender without its own limit.
+    #[serde(default)]
+    pub daily_limit: Option<Microcredits>,
+    /// The daily limits of specific senders, by address.
+    #[serde(default)]
+    pub daily_limits: BTreeMap<String, Microcredits>,
+    /// The recipients that may receive credits, if only specific recipients may.
+    #[serde(default)]
+    pub allowlist: Option<BTreeSet<String>>,
+    /// The recipients that may never receive credits.
+    #[serde(default)]
+    pub denylist: BTreeSet<String>,
+    /// The maximum priority fee.
+    #[serde(default)]
+    pub max_priority_fee: Option<Microcredits>,
+    /// The amount from which a spending must be approved first.
+    #[serde(default)]
+    pub confirmation_threshold: Option<Microcredits>,
+    /// The spending of each sender, by address.
+    #[serde(skip)]
+    spending: Mutex<BTreeMap<String, DailySpending>>,
+    /// The unused approvals.
+    #[serde(skip)]
+    approvals: Mutex<Vec<Approval>>,
+    /// The path to persist the spending to, after each authorization.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl SpendingPolicy {
+    /// Loads the policy from the given JSON file, ensuring every address in it is valid.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents =
+            fs::read_to_string(path).map_err(|error| anyhow!("Failed to read '{}': {error}", path.display()))?;
+        let policy: Self = serde_json::from_str(&contents)?;
+
+        // Ensure every address is valid, as a mistyped address would never match.
+        let addresses = policy.daily_limits.keys().chain(policy.allowlist.iter().flatten()).chain(&policy.denylist);
+        for address in addresses {
+            Address::<N>::from_str(address)
+                .map_err(|_| anyhow!("Invalid address '{address}' in the spending policy"))?;
+        }
+        Ok(policy)
+    }
+
+    /// Persists the spending to the given path, resuming from any spending that was previously persisted,
+    /// so that the daily limits hold across restarts.
+    pub fn persist_to(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            self.spending = Mutex::new(serde_json::from_str(&fs::read_to_string(&path)?)?);
+        }
+        self.path = Some(path.as_ref().to_path_buf());
+        Ok(self)
+    }
+
+    /// Installs the policy, so that every `Credits` function checks it before it signs, and returns it.
+    pub fn install(self) -> Result<Arc<Self>> {
+        let policy = Arc::new(self);
+        *POLICY.write().map_err(|_| anyhow!("The spending policy is poisoned"))? = Some(policy.clone());
+        Ok(policy)
+    }
+
+    /// Uninstalls the installed policy, if any.
+    pub fn uninstall() -> Result<()> {
+        *POLICY.write().map_err(|_| anyhow!("The spending policy is poisoned"))? = None;
+        Ok(())
+    }
+
+    /// Returns the installed policy, if any.
+    pub fn installed() -> Result<Option<Arc<Self>>> {
+        Ok(POLICY.read().map_err(|_| anyhow!("The spending policy is poisoned"))?.clone())
+    }
+
+    /// Approves the next spending of the given amount from the sender to the recipient,
+    /// which reaches the confirmation threshold.
+    pub fn approve(
+        &self,
+        sender: &str,
+        recipient: &str,
+        amount_in_microcredits: impl Into<Microcredits>,
+    ) -> Result<()> {
+        let approval = Approval {
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            amount: amount_in_microcredits.into(),
+        };
+        self.lock(&self.approvals)?.push(approval);
+        Ok(())
+    }
+
+    /// Returns the total cost the sender has authorized today.
+    pub fn spent_today(&self, sender: &str) -> Result<Microcredits> {
+        Ok(self.spent_on(&*self.lock(&self.spending)?, sender, today()?))
+    }
+
+    /// Checks the given spending against the policy, without recording it.
+    pub fn check(&self, spend: &Spend) -> Result<()> {
+        let spending = self.lock(&self.spending)?;
+        let approvals = self.lock(&self.approvals)?;
+        self.check_with(spend, &spending, &approvals, today()?).map(|_| ())
+    }
+
+    /// Checks the given spending against the policy, and records it towards the daily limit of the sender,
+    /// consuming its approval if it needed one.
+    pub(crate) fn enforce(&self, spend: &Spend) -> Result<Charge> {
+        let mut spending = self.lock(&self.spending)?;
+        let mut approvals = self.lock(&self.approvals)?;
+        let day = today()?;
+
+        // Check the spending.
+        let (total, approval) = self.check_with(spend, &spending, &approvals, day)?;
+        // Consume the approval.
+        if let Some(index) = approval {
+            approvals.remove(index);
+        }
+        // Record the spending.
+        let spent = self.spent_on(&spending, &spend.sender, day).checked_add(total)?;
+        spending.insert(spend.sender.clone(), DailySpending { day, spent });
+        self.persist(&spending)?;
+        Ok(Charge { day, total })
+    }
+
+    /// Removes the given charge from the daily spending of the sender, i.e. if its authorization failed.
+    ///
+    /// The charge is removed from the day it was recorded on, so nothing is refunded once that day has passed.
+    pub(crate) fn refund(&self, spend: &Spend, charge: Charge) -> Result<()> {
+        let mut spending = self.lock(&self.spending)?;
+        if let Some(daily) = spending.get_mut(&spend.sender).filter(|daily| daily.day == charge.day) {
+            daily.spent = Microcredits::new(daily.spent.saturating_sub(*charge.total));
+            self.persist(&spending)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the given spending against the policy on the given day,
+    /// returning its total cost and the index of its approval, if it needed one.
+    fn check_with(
+        &self,
+        spend: &Spend,
+        spending: &BTreeMap<String, DailySpending>,
+        approvals: &[Approval],
+        day: u64,
+    ) -> Result<(Microcredits, Option<usize>)> {
+        let mut approval = None;
+
+        if let Some(recipient) = &spend.recipient {
+            // Ensure the recipient is not denied.
+            if self.denylist.contains(recipient) {
+                return Err(PolicyViolation::RecipientDenied { recipient: recipient.clone() }.into());
+            }
+            // Ensure the recipient is allowed.
+            if let Some(allowlist) = &self.allowlist {
+                if !allowlist.contains(recipient) {
+                    return Err(PolicyViolation::RecipientNotAllowed { recipient: recipient.clone() }.into());
+                }
+            }
+            // Ensure an amount that reaches the threshold was approved.
+            if let Some(threshold) = self.confirmation_threshold {
+                if spend.amount >= threshold {
+                    let index = approvals.iter().position(|approval| {
+                        approval.sender == spend.sender
+                            && &approval.recipient == recipient
+                            && approval.amount == spend.amount
+                    });
+                    match index {
+                        Some(index) => approval = Some(index),
+                        None => {
+                            return Err(PolicyViolation::ConfirmationRequired { amount: spend.amount, threshold }.into())
+                        }
+                    }
+                }
+            }
+        }
+
+        // Ensure the priority fee does not exceed the maximum.
+        if let Some(max_priority_fee) = self.max_priority_fee {
+            if spend.priority_fee > max_priority_fee {
+                return Err(
+                    PolicyViolation::PriorityFeeTooHigh { priority_fee: spend.priority_fee, max_priority_fee }.into()
+                );
+            }
+        }
+
+        // Ensure the total cost does not exceed the daily limit of the sender.
+        let total = spend.total()?;
+        if let Some(limit) = self.daily_limits.get(&spend.sender).or(self.daily_limit.as_ref()) {
+            let spent = self.spent_on(spending, &spend.sender, day);
+            if spent.checked_add(total).map_or(true, |sum| sum > *limit) {
+                return Err(PolicyViolation::DailyLimitExceeded {
+                    sender: spend.sender.clone(),
+                    limit: *limit,
+                    spent,
+                    requested: total,
+                }
+                .into());
+            }
+        }
+        Ok((total, approval))
+    }
+
+    /// Returns the total cost the sender authorized on the given day.
+    fn spent_on(&self, spending: &BTreeMap<String, DailySpending>, sender: &str, day: u64) -> Microcredits {
+        match spending.get(sender) {
+            Some(daily) if daily.day == day => daily.spent,
+            _ => Microcredits::ZERO,
+        }
+    }
+
+    /// Locks the given state of the policy.
+    fn lock<'a, T>(&self, state: &'a Mutex<T>) -> Result<MutexGuard<'a, T>> {
+        state.lock().map_err(|_| anyhow!("The spending policy is poisoned"))
+    }
+
+    /// Persists the given spending to the path, if one is set.
+    fn persist(&self, spending: &BTreeMap<String, DailySpending>) -> Result<()> {
+        if let Some(path) = &self.path {
+            write_atomically(path, spending)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the current UTC day, in days since the Unix epoch.
+fn today() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / SECONDS_PER_DAY)
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{api, Microcredits, N};
+use snarkvm::{
+    console::{
+        account::Address,
+        program::{Identifier, Literal, Plaintext, Value},
+    },
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{bail, Result};
+use core::str::FromStr;
+
+/// The bond state of a staker, as stored in the `bonded` mapping.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondState {
+    /// The validator the staker is bonded to.
+    pub validator: Address<N>,
+    /// The amount bonded in microcredits.
+    pub microcredits: Microcredits,
+}
+
+/// The unbond state of a staker, as stored in the `unbonding` mapping.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnbondState {
+    /// The amount unbonding in microcredits.
+    pub microcredits: Microcredits,
+    /// The block height at which the amount may be claimed.
+    pub height: u32,
+}
+
+/// Returns the latest block height from the given endpoint.
+pub fn get_latest_height(endpoint: &str) -> Result<u32> {
+    api::get_request(&format!("{endpoint}/latest/height"))
+}
+
+/// Returns the public balance of the given address, as stored in the `account` mapping.
+pub fn get_public_balance(endpoint: &str, address: &str) -> Result<Microcredits> {
+    match get_mapping_value(endpoint, "account", address)? {
+        Some(value) => Ok(Microcredits::new(to_u64(&value)?)),
+        None => Ok(Microcredits::ZERO),
+    }
+}
+
+/// Returns the bond state of the given address, if it is bonded.
+pub fn get_bond_state(endpoint: &str, address: &str) -> Result<Option<BondState>> {
+    match get_mapping_value(endpoint, "bonded", address)? {
+        Some(value) => Ok(Some(BondState {
+            validator: to_address(&find_member(&value, "validator")?)?,
+            microcredits: Microcredits::new(to_u64(&find_member(&value, "microcredits")?)?),
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Returns the unbond state of the given address, if it is unbonding.
+pub fn get_unbond_state(endpoint: &str, address: &str) -> Result<Option<UnbondState>> {
+    match get_mapping_value(endpoint, "unbonding", address)? {
+        Some(value) => Ok(Some(UnbondState {
+            microcredits: Microcredits::new(to_u64(&find_member(&value, "microcredits")?)?),
+            height: to_u32(&find_member(&value, "height")?)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Returns the value of the given key in the given `credits.aleo` mapping, if it exists.
+fn get_mapping_value(endpoint: &str, mapping: &str, key: &str) -> Result<Option<Value<N>>> {
+    // Ensure the key is a valid address.
+    let key = Address::<N>::from_str(key)?;
+    // Retrieve the mapping value.
+    api::get_request(&format!("{endpoint}/program/credits.aleo/mapping/{mapping}/{key}"))
+}
+
+/// Returns the member of the given struct value.
+fn find_member(value: &Value<N>, member: &str) -> Result<Value<N>> {
+    value.find(&[Identifier::<N>::from_str(member)?])
+}
+
+/// Returns the given value as an address.
+fn to_address(value: &Value<N>) -> Result<Address<N>> {
+    match value {
+        Value::Plaintext(Plaintext::Literal(Literal::Address(address), _)) => Ok(*address),
+        _ => bail!("Expected an address, found '{value}'"),
+    }
+}
+
+/// Returns the given value as a `u64`.
+fn to_u64(value: &Value<N>) -> Result<u64> {
+    match value {
+        Value::Plaintext(Plaintext::Literal(Literal::U64(integer), _)) => Ok(**integer),
+        _ => bail!("Expected a u64, found '{value}'"),
+    }
+}
+
+/// Returns the given value as a `u32`.
+fn to_u32(value: &Value<N>) -> Result<u32> {
+    match value {
+        Value::Plaintext(Plaintext::Literal(Literal::U32(integer), _)) => Ok(**integer),
+        _ => bail!("Expected a u32, found '{value}'"),
+    }
+}
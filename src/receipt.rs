@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Microcredits, N};
+use snarkvm::{
+    console::{
+        account::Address,
+        program::{Literal, Plaintext},
+    },
+    ledger::block::{Input, Transaction},
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{anyhow, Result};
+
+/// The fee paid by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeReceipt {
+    /// The ID of the fee transition.
+    pub transition_id: String,
+    /// The function name of the fee transition, i.e. `fee_public` or `fee_private`.
+    pub function_name: String,
+    /// The address that paid the fee, if it is public.
+    pub payer: Option<Address<N>>,
+    /// The base fee paid.
+    pub base_fee: Microcredits,
+    /// The priority fee paid.
+    pub priority_fee: Microcredits,
+}
+
+impl FeeReceipt {
+    /// Returns the total fee paid.
+    pub fn total(&self) -> Result<Microcredits> {
+        self.base_fee.checked_add(self.priority_fee)
+    }
+}
+
+/// A receipt of an executed transaction, i.e. to store in a ledger database.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// The transaction ID.
+    pub transaction_id: String,
+    /// The IDs of the transitions of the execution, excluding the fee.
+    pub transition_ids: Vec<String>,
+    /// The program ID of the function that was called.
+    pub program_id: String,
+    /// The name of the function that was called.
+    pub function_name: String,
+    /// The public inputs of the function, in order.
+    pub public_inputs: Vec<Plaintext<N>>,
+    /// The commitments of the output records, including any change record of the fee.
+    pub record_commitments: Vec<String>,
+    /// The fee, if the transaction paid one.
+    pub fee: Option<FeeReceipt>,
+}
+
+impl Receipt {
+    /// Returns the first address among the public inputs, i.e. the recipient of a transfer or the validator of a bond.
+    pub fn address(&self) -> Option<Address<N>> {
+        self.public_inputs.iter().find_map(|input| match input {
+            Plaintext::Literal(Literal::Address(address), _) => Some(*address),
+            _ => None,
+        })
+    }
+
+    /// Returns the first amount among the public inputs, i.e. the amount of a transfer or a bond.
+    pub fn amount(&self) -> Option<Microcredits> {
+        self.public_inputs.iter().find_map(|input| match input {
+            Plaintext::Literal(Literal::U64(amount), _) => Some(Microcredits::new(**amount)),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&Transaction<N>> for Receipt {
+    type Error = anyhow::Error;
+
+    /// Returns the receipt of the given execution transaction.
+    fn try_from(transaction: &Transaction<N>) -> Result<Self> {
+        let execution = transaction
+            .execution()
+            .ok_or_else(|| anyhow!("The transaction '{}' is not an execution", transaction.id()))?;
+        // The function that was called is the last transition of the execution.
+        let root = execution
+            .transitions()
+            .last()
+            .ok_or_else(|| anyhow!("The transaction '{}' has no transitions", transaction.id()))?;
+
+        // Retrieve the fee.
+        let fee = match transaction.fee_transition() {
+            Some(fee) => Some(FeeReceipt {
+                transition_id: fee.transition_id().to_string(),
+                function_name: fee.transition().function_name().to_string(),
+                payer: fee.payer(),
+                base_fee: Microcredits::new(*fee.base_amount()?),
+                priority_fee: Microcredits::new(*fee.priority_amount()?),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            transaction_id: transaction.id().to_string(),
+            transition_ids: execution.transitions().map(|transition| transition.id().to_string()).collect(),
+            program_id: root.program_id().to_string(),
+            function_name: root.function_name().to_string(),
+            public_inputs: root
+                .inputs()
+                .iter()
+                .filter_map(|input| match input {
+                    Input::Public(_, Some(plaintext)) => Some(plaintext.clone()),
+                    _ => None,
+                })
+                .collect(),
+            record_commitments: transaction.commitments().map(|commitment| commitment.to_string()).collect(),
+            fee,
+        })
+    }
+}
+
+impl TryFrom<Transaction<N>> for Receipt {
+    type Error = anyhow::Error;
+
+    /// Returns the receipt of the given execution transaction.
+    fn try_from(transaction: Transaction<N>) -> Result<Self> {
+        Self::try_from(&transaction)
+    }
+}
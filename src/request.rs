@@ -0,0 +1,233 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Authorized, CreditsCall, ExecutionMode, FeeEstimator, FeeLevel, Microcredits, SecretString, N};
+
+use anyhow::{anyhow, ensure, Result};
+use rand::{CryptoRng, Rng};
+
+/// A `credits.aleo` function that a request may call.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Function {
+    /// `bond_public`.
+    BondPublic,
+    /// `unbond_public`.
+    UnbondPublic,
+    /// `unbond_delegator_as_validator`.
+    UnbondDelegatorAsValidator,
+    /// `claim_unbond_public`.
+    ClaimUnbondPublic,
+    /// `set_validator_state`.
+    SetValidatorState,
+    /// `transfer_public`.
+    TransferPublic,
+    /// `transfer_public_to_private`.
+    TransferPublicToPrivate,
+}
+
+impl Function {
+    /// Returns the function name.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::BondPublic => "bond_public",
+            Self::UnbondPublic => "unbond_public",
+            Self::UnbondDelegatorAsValidator => "unbond_delegator_as_validator",
+            Self::ClaimUnbondPublic => "claim_unbond_public",
+            Self::SetValidatorState => "set_validator_state",
+            Self::TransferPublic => "transfer_public",
+            Self::TransferPublicToPrivate => "transfer_public_to_private",
+        }
+    }
+}
+
+/// A request to call a `credits.aleo` function, whose inputs are set by name rather than by position.
+///
+/// If no priority fee is set, it is suggested by the fee estimator at the fee level, which default to
+/// `FeeEstimator::default()` and `FeeLevel::Medium`. The execution mode defaults to `ExecutionMode::Broadcast`.
+pub struct CreditsRequest {
+    /// The function to call.
+    function: Function,
+    /// The private key of the caller.
+    private_key: Option<SecretString>,
+    /// The address input, i.e. the recipient, validator, or delegator.
+    to: Option<String>,
+    /// The amount input in microcredits.
+    amount: Option<Microcredits>,
+    /// The 'is_open' input of `set_validator_state`.
+    is_open: Option<bool>,
+    /// The priority fee in microcredits, if it is not estimated.
+    priority_fee: Option<Microcredits>,
+    /// The fee level to estimate the priority fee at.
+    fee_level: FeeLevel,
+    /// The fee estimator.
+    estimator: FeeEstimator,
+    /// The execution mode.
+    mode: ExecutionMode,
+}
+
+impl CreditsRequest {
+    /// Initializes a request to call `bond_public`, which requires `to` the validator and an `amount`.
+    pub fn bond_public() -> Self {
+        Self::new(Function::BondPublic)
+    }
+
+    /// Initializes a request to call `unbond_public`, which requires an `amount`.
+    pub fn unbond_public() -> Self {
+        Self::new(Function::UnbondPublic)
+    }
+
+    /// Initializes a request to call `unbond_delegator_as_validator`, which requires `to` the delegator.
+    pub fn unbond_delegator_as_validator() -> Self {
+        Self::new(Function::UnbondDelegatorAsValidator)
+    }
+
+    /// Initializes a request to call `claim_unbond_public`, which takes no inputs.
+    pub fn claim_unbond_public() -> Self {
+        Self::new(Function::ClaimUnbondPublic)
+    }
+
+    /// Initializes a request to call `set_validator_state`, which requires `is_open`.
+    pub fn set_validator_state() -> Self {
+        Self::new(Function::SetValidatorState)
+    }
+
+    /// Initializes a request to call `transfer_public`, which requires `to` the recipient and an `amount`.
+    pub fn transfer_public() -> Self {
+        Self::new(Function::TransferPublic)
+    }
+
+    /// Initializes a request to call `transfer_public_to_private`, which requires `to` the recipient and an `amount`.
+    pub fn transfer_public_to_private() -> Self {
+        Self::new(Function::TransferPublicToPrivate)
+    }
+
+    /// Sets the private key of the caller.
+    pub fn from(mut self, private_key: impl Into<SecretString>) -> Self {
+        self.private_key = Some(private_key.into());
+        self
+    }
+
+    /// Sets the address input, i.e. the recipient, validator, or delegator.
+    pub fn to(mut self, address: impl Into<String>) -> Self {
+        self.to = Some(address.into());
+        self
+    }
+
+    /// Sets the amount input in microcredits.
+    pub fn amount(mut self, amount_in_microcredits: impl Into<Microcredits>) -> Self {
+        self.amount = Some(amount_in_microcredits.into());
+        self
+    }
+
+    /// Sets the 'is_open' input of `set_validator_state`.
+    pub fn is_open(mut self, is_open: bool) -> Self {
+        self.is_open = Some(is_open);
+        self
+    }
+
+    /// Sets the priority fee in microcredits, instead of estimating it.
+    pub fn priority_fee(mut self, priority_fee_in_microcredits: impl Into<Microcredits>) -> Self {
+        self.priority_fee = Some(priority_fee_in_microcredits.into());
+        self
+    }
+
+    /// Sets the fee level to estimate the priority fee at.
+    pub fn fee_level(mut self, fee_level: FeeLevel) -> Self {
+        self.fee_level = fee_level;
+        self
+    }
+
+    /// Sets the fee estimator, i.e. to sample a different API endpoint.
+    pub fn estimator(mut self, estimator: FeeEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Sets the execution mode.
+    pub fn mode(mut self, mode: ExecutionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the call of the request, ensuring the function receives exactly the inputs it takes.
+    pub fn call(&self) -> Result<CreditsCall> {
+        let function = self.function.name();
+        let to = || self.to.clone().ok_or_else(|| anyhow!("'{function}' requires an address, set with `to`"));
+        let amount = || self.amount.ok_or_else(|| anyhow!("'{function}' requires an amount"));
+        let is_open = || self.is_open.ok_or_else(|| anyhow!("'{function}' requires 'is_open'"));
+
+        // Construct the call, and determine which of the address, amount, and 'is_open' inputs it takes.
+        let (call, takes_to, takes_amount, takes_is_open) = match self.function {
+            Function::BondPublic => {
+                (CreditsCall::BondPublic { validator: to()?, amount_in_microcredits: amount()? }, true, true, false)
+            }
+            Function::UnbondPublic => {
+                (CreditsCall::UnbondPublic { amount_in_microcredits: amount()? }, false, true, false)
+            }
+            Function::UnbondDelegatorAsValidator => {
+                (CreditsCall::UnbondDelegatorAsValidator { delegator: to()? }, true, false, false)
+            }
+            Function::ClaimUnbondPublic => (CreditsCall::ClaimUnbondPublic, false, false, false),
+            Function::SetValidatorState => (CreditsCall::SetValidatorState { is_open: is_open()? }, false, false, true),
+            Function::TransferPublic => {
+                (CreditsCall::TransferPublic { recipient: to()?, amount_in_microcredits: amount()? }, true, true, false)
+            }
+            Function::TransferPublicToPrivate => {
+                let call = CreditsCall::TransferPublicToPrivate { recipient: to()?, amount_in_microcredits: amount()? };
+                (call, true, true, false)
+            }
+        };
+
+        // Ensure no input is set that the function does not take, as it is likely meant for another input.
+        ensure!(takes_to || self.to.is_none(), "'{function}' does not take an address");
+        ensure!(takes_amount || self.amount.is_none(), "'{function}' does not take an amount");
+        ensure!(takes_is_open || self.is_open.is_none(), "'{function}' does not take 'is_open'");
+        Ok(call)
+    }
+
+    /// Returns the priority fee in microcredits, estimating it if it is not set.
+    pub fn resolve_priority_fee(&self) -> Result<Microcredits> {
+        match self.priority_fee {
+            Some(priority_fee) => Ok(priority_fee),
+            None => Ok(Microcredits::new(self.estimator.suggest(self.fee_level)?)),
+        }
+    }
+
+    /// Returns the authorization of the request.
+    pub fn authorize(&self, rng: &mut (impl Rng + CryptoRng)) -> Result<Authorized<N>> {
+        // Validate the inputs before the priority fee is estimated.
+        let call = self.call()?;
+        let private_key = self
+            .private_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("'{}' requires a private key, set with `from`", self.function.name()))?;
+        // Construct the authorization.
+        call.authorize(private_key, self.resolve_priority_fee()?, self.mode, rng)
+    }
+
+    /// Initializes a request to call the given function, with the default fee estimation and execution mode.
+    fn new(function: Function) -> Self {
+        Self {
+            function,
+            private_key: None,
+            to: None,
+            amount: None,
+            is_open: None,
+            priority_fee: None,
+            fee_level: FeeLevel::Medium,
+            estimator: FeeEstimator::default(),
+            mode: ExecutionMode::Broadcast,
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::API_URL;
+
+use anyhow::{bail, ensure, Result};
+use serde::de::DeserializeOwned;
+use std::{fmt, thread, time::Duration};
+
+/// A class of HTTP response statuses that may be retried.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatusClass {
+    /// The `408 Request Timeout` status.
+    RequestTimeout,
+    /// The `429 Too Many Requests` status.
+    TooManyRequests,
+    /// Any `5xx` status.
+    ServerError,
+}
+
+impl StatusClass {
+    /// Returns `true` if the given HTTP status code belongs to this class.
+    pub const fn contains(&self, status: u16) -> bool {
+        match self {
+            Self::RequestTimeout => status == 408,
+            Self::TooManyRequests => status == 429,
+            Self::ServerError => status >= 500 && status < 600,
+        }
+    }
+}
+
+/// The error returned when an endpoint rejects a request with a client error that is not retryable.
+///
+/// Unlike a timeout or a server error, this means the request was definitely not accepted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestRejected {
+    /// The endpoint that rejected the request.
+    pub endpoint: String,
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response body.
+    pub message: String,
+}
+
+impl fmt::Display for RequestRejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} rejected the request with status {}: {}", self.endpoint, self.status, self.message)
+    }
+}
+
+impl std::error::Error for RequestRejected {}
+
+/// A policy for retrying requests with exponential backoff, across a list of endpoints tried in order.
+///
+/// Connection errors, timeouts, and unsuccessful responses that cannot be read are always retried.
+/// Any other unsuccessful response is retried only if its status belongs to one of the retryable status classes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The API endpoints, with the primary endpoint first, followed by the fallback endpoints.
+    endpoints: Vec<String>,
+    /// The maximum number of attempts on each endpoint.
+    max_attempts: u32,
+    /// The backoff before the first retry, which doubles on every subsequent retry.
+    initial_backoff: Duration,
+    /// The maximum backoff between two attempts.
+    max_backoff: Duration,
+    /// The timeout of each attempt.
+    timeout: Duration,
+    /// The status classes to retry.
+    retryable: Vec<StatusClass>,
+}
+
+impl Default for RetryPolicy {
+    /// Returns the default policy for the public API endpoint.
+    fn default() -> Self {
+        Self::new(API_URL)
+    }
+}
+
+impl RetryPolicy {
+    /// Initializes a new policy for the given primary endpoint, with 3 attempts, a backoff from 1 to 16 seconds,
+    /// a timeout of 120 seconds, and retries on request timeouts, rate limits, and server errors.
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoints: vec![endpoint.to_string()],
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(16),
+            timeout: Duration::from_secs(120),
+            retryable: vec![StatusClass::RequestTimeout, StatusClass::TooManyRequests, StatusClass::ServerError],
+        }
+    }
+
+    /// Adds a fallback endpoint, which is tried once every previous endpoint is exhausted.
+    pub fn with_fallback(mut self, endpoint: &str) -> Self {
+        self.endpoints.push(endpoint.to_string());
+        self
+    }
+
+    /// Sets the maximum number of attempts on each endpoint.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the backoff before the first retry, and the maximum backoff between two attempts.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the timeout of each attempt.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the status classes to retry.
+    pub fn with_retryable(mut self, retryable: Vec<StatusClass>) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Returns the endpoints, in the order they are tried.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Returns the maximum number of attempts on each endpoint.
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the backoff before the given retry, starting from `1` for the first retry.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Returns `true` if the given HTTP status code should be retried.
+    pub fn is_retryable(&self, status: u16) -> bool {
+        self.retryable.iter().any(|class| class.contains(status))
+    }
+
+    /// Performs a POST request with the given body to the given path of each endpoint in turn,
+    /// returning the first successful deserialized response.
+    ///
+    /// The same body is sent on every attempt. A client error that is not retryable is returned as `RequestRejected`.
+    pub(crate) fn post<T: DeserializeOwned>(&self, path: &str, body: &str) -> Result<T> {
+        ensure!(!self.endpoints.is_empty(), "The retry policy has no endpoints");
+        ensure!(self.max_attempts > 0, "The retry policy must allow at least one attempt");
+
+        // Initialize the client.
+        let client = reqwest::blocking::Client::builder().timeout(self.timeout).build()?;
+
+        let mut errors = Vec::new();
+        for endpoint in &self.endpoints {
+            for attempt in 0..self.max_attempts {
+                // Wait before retrying.
+                if attempt > 0 {
+                    thread::sleep(self.backoff(attempt));
+                }
+
+                // Send the request.
+                let response = match client
+                    .post(format!("{endpoint}{path}"))
+                    .header("Content-Type", "application/json")
+                    .body(body.to_string())
+                    .send()
+                {
+                    Ok(response) => response,
+                    // Retry on connection errors and timeouts.
+                    Err(error) => {
+                        errors.push(format!("{endpoint} (attempt {}): {error}", attempt + 1));
+                        continue;
+                    }
+                };
+
+                // Return the response, if it is successful.
+                let status = response.status();
+                if status.is_success() {
+                    return Ok(response.json()?);
+                }
+                // Otherwise, retry if the response could not be read, or if the status is retryable.
+                let message = match response.text() {
+                    Ok(text) => text,
+                    Err(error) => {
+                        errors.push(format!("{endpoint} (attempt {}): {status} {error}", attempt + 1));
+                        continue;
+                    }
+                };
+                match self.is_retryable(status.as_u16()) {
+                    true => errors.push(format!("{endpoint} (attempt {}): {status} {message}", attempt + 1)),
+                    // A client error means the request was definitely not accepted.
+                    false if status.is_client_error() => {
+                        let status = status.as_u16();
+                        return Err(RequestRejected { endpoint: endpoint.clone(), status, message }.into());
+                    }
+                    false => bail!("{endpoint} (attempt {}): {status} {message}", attempt + 1),
+                }
+            }
+        }
+        bail!("The request failed on every endpoint:\n  {}", errors.join("\n  "))
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{api, file::write_atomically, secret::parse_private_key, Microcredits, N};
+use snarkvm::{
+    console::{
+        account::{GraphKey, ViewKey},
+        program::{Entry, Identifier, Literal, Plaintext, ProgramID, Record},
+        types::Field,
+    },
+    ledger::block::{Block, Transaction, Transition},
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{bail, ensure, Result};
+use core::{ops::Range, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+/// A `credits.aleo` record owned by the scanned view key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedRecord {
+    /// The commitment of the record.
+    commitment: Field<N>,
+    /// The tag of the record, which is published when the record is spent.
+    tag: Field<N>,
+    /// The height of the block that created the record, if it is known.
+    height: Option<u32>,
+    /// The decrypted record.
+    record: Record<N, Plaintext<N>>,
+}
+
+impl OwnedRecord {
+    /// Decrypts the `credits.aleo` record that the given transaction created for the given view key,
+    /// i.e. the output record of `transfer_public_to_private` for its recipient.
+    ///
+    /// As the transaction was not found in a block, the height of the record is unknown.
+    pub fn from_transaction(transaction: &Transaction<N>, view_key: &ViewKey<N>) -> Result<Self> {
+        let sk_tag = GraphKey::try_from(view_key)?.sk_tag();
+        // Skip the fee, whose change record belongs to the sender.
+        for transition in transaction.transitions().filter(|transition| !transition.is_fee_private()) {
+            if let Some(record) = Self::from_transition(transition, view_key, sk_tag, None)?.into_iter().next() {
+                return Ok(record);
+            }
+        }
+        bail!("The transaction '{}' has no record for the view key", transaction.id())
+    }
+
+    /// Returns the commitment of the record.
+    pub const fn commitment(&self) -> Field<N> {
+        self.commitment
+    }
+
+    /// Returns the tag of the record.
+    pub const fn tag(&self) -> Field<N> {
+        self.tag
+    }
+
+    /// Returns the height of the block that created the record, if it is known.
+    pub const fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    /// Returns the decrypted record, i.e. to spend it as the input of a private transfer.
+    pub const fn record(&self) -> &Record<N, Plaintext<N>> {
+        &self.record
+    }
+
+    /// Returns the amount of the record in microcredits.
+    pub fn microcredits(&self) -> Result<Microcredits> {
+        match self.record.find(&[Identifier::<N>::from_str("microcredits")?])? {
+            Entry::Private(Plaintext::Literal(Literal::U64(amount), _))
+            | Entry::Public(Plaintext::Literal(Literal::U64(amount), _)) => Ok(Microcredits::new(*amount)),
+            _ => bail!("The record '{}' does not hold microcredits", self.commitment),
+        }
+    }
+
+    /// Returns the serial number of the record, which requires the private key of the owner.
+    pub fn serial_number(&self, private_key: &str) -> Result<Field<N>> {
+        Record::<N, Plaintext<N>>::serial_number(*parse_private_key(private_key)?, self.commitment)
+    }
+
+    /// Decrypts the `credits.aleo` records in the given transition that are owned by the given view key.
+    fn from_transition(
+        transition: &Transition<N>,
+        view_key: &ViewKey<N>,
+        sk_tag: Field<N>,
+        height: Option<u32>,
+    ) -> Result<Vec<Self>> {
+        let program_id = ProgramID::<N>::from_str("credits.aleo")?;
+        let record_name = Identifier::<N>::from_str("credits")?;
+
+        // Only `credits.aleo` records are decrypted.
+        if *transition.program_id() != program_id {
+            return Ok(vec![]);
+        }
+
+        let mut records = Vec::new();
+        for (commitment, ciphertext) in transition.records() {
+            // Skip the records that are not owned by the view key.
+            if !ciphertext.is_owner(view_key) {
+                continue;
+            }
+            // Decrypt the record, and ensure it matches its commitment.
+            let record = ciphertext.decrypt(view_key)?;
+            ensure!(
+                record.to_commitment(&program_id, &record_name)? == *commitment,
+                "The record '{commitment}' does not match its commitment"
+            );
+            // Compute the tag of the record.
+            let tag = Record::<N, Plaintext<N>>::tag(sk_tag, *commitment)?;
+            records.push(Self { commitment: *commitment, tag, height, record });
+        }
+        Ok(records)
+    }
+}
+
+/// The progress of a scan, as persisted between runs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The next block height to scan, if any block has been scanned.
+    height: Option<u32>,
+    /// The unspent records, by commitment.
+    records: BTreeMap<String, OwnedRecord>,
+}
+
+/// A scanner that walks blocks from the explorer API, and collects the unspent `credits.aleo` records of a view key.
+///
+/// Computing the serial number of a record requires the private key, so a spent record is instead detected by its
+/// tag, which the view key derives and the chain publishes alongside the serial number of every spent record.
+pub struct RecordScanner {
+    /// The API endpoint to retrieve blocks from.
+    endpoint: String,
+    /// The view key to decrypt records with.
+    view_key: Zeroizing<ViewKey<N>>,
+    /// The secret key to compute the tags of records with.
+    sk_tag: Field<N>,
+    /// The progress of the scan.
+    checkpoint: Checkpoint,
+    /// The path to persist the progress to, after each scan.
+    path: Option<PathBuf>,
+}
+
+impl RecordScanner {
+    /// Initializes a new scanner for the given API endpoint and view key.
+    pub fn new(endpoint: &str, view_key: ViewKey<N>) -> Result<Self> {
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            sk_tag: GraphKey::try_from(&view_key)?.sk_tag(),
+            view_key: Zeroizing::new(view_key),
+            checkpoint: Checkpoint::default(),
+            path: None,
+        })
+    }
+
+    /// Persists the progress to the given path, resuming from any progress that was previously persisted.
+    pub fn persist_to(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            self.checkpoint = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        }
+        self.path = Some(path.as_ref().to_path_buf());
+        Ok(self)
+    }
+
+    /// Returns the next block height to scan, if any block has been scanned.
+    pub const fn height(&self) -> Option<u32> {
+        self.checkpoint.height
+    }
+
+    /// Returns the unspent records found so far, in the order they were created.
+    pub fn unspent(&self) -> Vec<OwnedRecord> {
+        let mut records = self.checkpoint.records.values().cloned().collect::<Vec<_>>();
+        records.sort_by_key(|record| record.height);
+        records
+    }
+
+    /// Scans the blocks in the given range of heights, returning the unspent records found so far.
+    ///
+    /// Blocks below the checkpoint were already scanned and are skipped. The range may not start after the
+    /// checkpoint, as a record spent in a skipped block would then be reported as unspent.
+    pub fn scan(&mut self, heights: Range<u32>) -> Result<Vec<OwnedRecord>> {
+        let start = match self.checkpoint.height {
+            Some(height) => {
+                ensure!(heights.start <= height, "The scan must resume from block {height}, found {}", heights.start);
+                height
+            }
+            None => heights.start,
+        };
+
+        for height in start..heights.end {
+            // Retrieve and scan the block.
+            let block = api::get_block(&self.endpoint, height)?;
+            let found = self.scan_block(&block)?;
+            // Persist the progress whenever the records change.
+            if found {
+                self.persist()?;
+            }
+        }
+        self.persist()?;
+        Ok(self.unspent())
+    }
+
+    /// Scans the given block, returning `true` if the unspent records changed.
+    ///
+    /// The blocks must be scanned in order of height.
+    pub fn scan_block(&mut self, block: &Block<N>) -> Result<bool> {
+        let changed = self.scan_transitions(block.height(), block.transitions())?;
+        self.checkpoint.height = Some(block.height() + 1);
+        Ok(changed)
+    }
+
+    /// Scans the given transitions from the block at the given height, returning `true` if the unspent records changed.
+    pub(crate) fn scan_transitions<'a>(
+        &mut self,
+        height: u32,
+        transitions: impl IntoIterator<Item = &'a Transition<N>>,
+    ) -> Result<bool> {
+        let mut tags = BTreeSet::new();
+        let mut changed = false;
+        for transition in transitions {
+            // Collect the tags of the spent records.
+            tags.extend(transition.tags().map(|tag| tag.to_string()));
+
+            for owned in OwnedRecord::from_transition(transition, &self.view_key, self.sk_tag, Some(height))? {
+                changed |= self.checkpoint.records.insert(owned.commitment.to_string(), owned).is_none();
+            }
+        }
+
+        // Remove the spent records.
+        let count = self.checkpoint.records.len();
+        self.checkpoint.records.retain(|_, record| !tags.contains(&record.tag.to_string()));
+        Ok(changed || self.checkpoint.records.len() != count)
+    }
+
+    /// Persists the progress to the path, if one is set.
+    fn persist(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            write_atomically(path, &self.checkpoint)?;
+        }
+        Ok(())
+    }
+}
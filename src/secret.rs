@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::N;
+use snarkvm::{
+    console::account::{Address, PrivateKey},
+    prelude::{Deserialize, Deserializer},
+};
+
+use anyhow::{anyhow, Result};
+use core::{fmt, ops::Deref, str::FromStr};
+use std::{fs, path::Path};
+use zeroize::Zeroizing;
+
+/// A string that holds secret material, such as a private key, and is zeroized when it is dropped.
+///
+/// As it dereferences to `str`, it may be passed directly as the private key of any `Credits` function.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    /// Initializes a new secret string, taking ownership of the given string.
+    pub fn new(secret: String) -> Self {
+        Self(Zeroizing::new(secret))
+    }
+
+    /// Reads the secret from the given environment variable.
+    pub fn from_env(name: &str) -> Result<Self> {
+        std::env::var(name).map(Self::new).map_err(|_| anyhow!("Missing the environment variable '{name}'"))
+    }
+
+    /// Reads the secret from the given file, ignoring any surrounding whitespace.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = Zeroizing::new(
+            fs::read_to_string(path).map_err(|error| anyhow!("Failed to read '{}': {error}", path.display()))?,
+        );
+        Ok(Self::new(contents.trim().to_string()))
+    }
+
+    /// Returns the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    /// Initializes a new secret string, taking ownership of the given string.
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    /// Initializes a new secret string from a copy of the given string.
+    fn from(secret: &str) -> Self {
+        Self::new(secret.to_string())
+    }
+}
+
+impl FromStr for SecretString {
+    type Err = anyhow::Error;
+
+    /// Initializes a new secret string from a copy of the given string.
+    fn from_str(secret: &str) -> Result<Self> {
+        Ok(Self::from(secret))
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    /// Returns the secret.
+    fn deref(&self) -> &Self::Target {
+        self.expose()
+    }
+}
+
+impl fmt::Debug for SecretString {
+    /// Redacts the secret.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretString(***)")
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    /// Deserializes the secret from a string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Parses the given private key, which is zeroized when it is dropped.
+pub(crate) fn parse_private_key(private_key: &str) -> Result<Zeroizing<PrivateKey<N>>> {
+    Ok(Zeroizing::new(PrivateKey::<N>::from_str(private_key)?))
+}
+
+/// Returns the address of the given private key, without leaving a copy of the parsed private key behind.
+pub(crate) fn address_of(private_key: &str) -> Result<Address<N>> {
+    Address::try_from(&*parse_private_key(private_key)?)
+}
@@ -0,0 +1,355 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    file::write_atomically,
+    get_bond_state,
+    get_latest_height,
+    get_unbond_state,
+    secret::address_of,
+    Authorized,
+    BondState,
+    Credits,
+    ExecutionMode,
+    Microcredits,
+    RequestRejected,
+    RetryPolicy,
+    N,
+};
+use snarkvm::{
+    console::account::Address,
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{bail, ensure, Result};
+use core::{fmt, str::FromStr};
+use rand::{CryptoRng, Rng};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The minimum amount in microcredits that may be bonded in a single `bond_public` call.
+pub const MIN_BOND_AMOUNT_IN_MICROCREDITS: u64 = 1_000_000;
+/// The minimum amount in microcredits that a delegator must keep bonded.
+pub const MIN_DELEGATOR_STAKE_IN_MICROCREDITS: u64 = 10_000_000;
+/// The minimum amount in microcredits that a validator must keep bonded.
+pub const MIN_VALIDATOR_STAKE_IN_MICROCREDITS: u64 = 1_000_000_000_000;
+
+/// A single step of a staking workflow.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum StakeStep {
+    /// Unbonds the given amount with `unbond_public`.
+    Unbond { microcredits: Microcredits },
+    /// Claims the unbonded amount with `claim_unbond_public`, once the unbonding period has passed.
+    Claim,
+    /// Bonds the given amount to the validator with `bond_public`.
+    Bond { validator: Address<N>, microcredits: Microcredits },
+}
+
+/// The outcome of advancing a staking workflow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StakeStatus {
+    /// The step was executed with the given transaction ID, and the workflow advanced to the next step.
+    Executed(String),
+    /// The step is waiting for the previous step to be finalized, or for the unbonding period to pass.
+    Waiting,
+    /// All steps have been executed.
+    Completed,
+}
+
+/// A step that was submitted, but is not yet known to be on chain.
+#[derive(Clone, Serialize, Deserialize)]
+struct PendingStep {
+    /// The predicted ID of the transaction.
+    transaction_id: String,
+    /// The authorization, so that the step is resubmitted without being re-signed.
+    authorization: Authorized<N>,
+}
+
+impl fmt::Debug for PendingStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PendingStep").field("transaction_id", &self.transaction_id).finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for PendingStep {
+    /// Returns `true` if both steps predict the same transaction, as its ID commits to the authorization.
+    fn eq(&self, other: &Self) -> bool {
+        self.transaction_id == other.transaction_id
+    }
+}
+
+impl Eq for PendingStep {}
+
+/// A multi-step staking workflow, such as unbonding all stake or moving stake to another validator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeWorkflow {
+    /// The address of the staker.
+    staker: Address<N>,
+    /// The steps of the workflow.
+    steps: Vec<StakeStep>,
+    /// The transaction IDs of the executed steps.
+    transaction_ids: Vec<String>,
+    /// The next step, if it was submitted but is not yet known to be on chain.
+    #[serde(default)]
+    pending: Option<PendingStep>,
+    /// The path to persist the workflow to, after each executed step.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl StakeWorkflow {
+    /// Plans a workflow that unbonds and claims the entire stake of the given staker.
+    pub fn unbond_all(endpoint: &str, staker: &str) -> Result<Self> {
+        let bond_state = Self::bond_state(endpoint, staker)?;
+        Self::plan_unbond_all(staker, &bond_state)
+    }
+
+    /// Plans a workflow that unbonds, claims, and bonds the entire stake of the given staker to the same validator.
+    pub fn rebond(endpoint: &str, staker: &str) -> Result<Self> {
+        let bond_state = Self::bond_state(endpoint, staker)?;
+        Self::plan_move_stake(staker, &bond_state, &bond_state.validator.to_string())
+    }
+
+    /// Plans a workflow that unbonds, claims, and bonds the entire stake of the given staker to the given validator.
+    pub fn move_stake(endpoint: &str, staker: &str, validator: &str) -> Result<Self> {
+        let bond_state = Self::bond_state(endpoint, staker)?;
+        ensure!(bond_state.validator.to_string() != validator, "The stake is already bonded to '{validator}'");
+        Self::plan_move_stake(staker, &bond_state, validator)
+    }
+
+    /// Plans a workflow that unbonds and claims the entire stake, given the staker's bond state.
+    pub fn plan_unbond_all(staker: &str, bond_state: &BondState) -> Result<Self> {
+        // Initialize the staker's address.
+        let staker = Address::<N>::from_str(staker)?;
+        // Construct the workflow.
+        let steps = vec![StakeStep::Unbond { microcredits: bond_state.microcredits }, StakeStep::Claim];
+        Self::new(staker, steps, bond_state.validator == staker)
+    }
+
+    /// Plans a workflow that unbonds, claims, and bonds the entire stake to the given validator,
+    /// given the staker's bond state.
+    pub fn plan_move_stake(staker: &str, bond_state: &BondState, validator: &str) -> Result<Self> {
+        // Initialize the staker's address.
+        let staker = Address::<N>::from_str(staker)?;
+        // Initialize the validator's address.
+        let validator = Address::<N>::from_str(validator)?;
+
+        // Ensure a validator only rebonds to themselves, as a validator's stake is not delegated.
+        let is_validator = bond_state.validator == staker;
+        ensure!(!is_validator || validator == staker, "A validator cannot move their own stake to another validator");
+
+        // Construct the workflow.
+        let microcredits = bond_state.microcredits;
+        let steps =
+            vec![StakeStep::Unbond { microcredits }, StakeStep::Claim, StakeStep::Bond { validator, microcredits }];
+        Self::new(staker, steps, is_validator)
+    }
+
+    /// Loads a workflow from the given path, and persists it to the same path after each executed step.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let mut workflow: Self = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        workflow.path = Some(path.as_ref().to_path_buf());
+        Ok(workflow)
+    }
+
+    /// Saves the workflow to the given path, and persists it to the same path after each executed step.
+    pub fn save(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.path = Some(path.as_ref().to_path_buf());
+        self.persist()
+    }
+
+    /// Returns the address of the staker.
+    pub const fn staker(&self) -> &Address<N> {
+        &self.staker
+    }
+
+    /// Returns the steps of the workflow.
+    pub fn steps(&self) -> &[StakeStep] {
+        &self.steps
+    }
+
+    /// Returns the transaction IDs of the executed steps.
+    pub fn transaction_ids(&self) -> &[String] {
+        &self.transaction_ids
+    }
+
+    /// Returns the predicted transaction ID of the next step, if it was submitted but is not yet known to be on chain.
+    pub fn pending_transaction_id(&self) -> Option<&str> {
+        self.pending.as_ref().map(|pending| pending.transaction_id.as_str())
+    }
+
+    /// Returns the next step to execute, if the workflow is not completed.
+    pub fn next_step(&self) -> Option<&StakeStep> {
+        self.steps.get(self.transaction_ids.len())
+    }
+
+    /// Returns `true` if all steps have been executed.
+    pub fn is_completed(&self) -> bool {
+        self.next_step().is_none()
+    }
+
+    /// Executes the next step of the workflow, if the chain state permits it.
+    ///
+    /// The step is persisted as pending before it is submitted. If it is not known to be accepted, i.e. after a crash
+    /// or a timeout, the next call looks up its transaction on chain, and otherwise resubmits the same authorization,
+    /// so that a step is never executed twice.
+    ///
+    /// This method should be called periodically until the workflow is completed.
+    pub fn advance(
+        &mut self,
+        endpoint: &str,
+        private_key: &str,
+        priority_fee_in_microcredits: impl Into<Microcredits>,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<StakeStatus> {
+        // Ensure the private key belongs to the staker.
+        ensure!(
+            address_of(private_key)? == self.staker,
+            "The private key does not belong to the staker '{}'",
+            self.staker
+        );
+
+        // Reconcile the step that was submitted, but is not yet known to be on chain.
+        if let Some(pending) = self.pending.clone() {
+            return self.reconcile(endpoint, pending);
+        }
+
+        // Retrieve the next step.
+        let step = match self.next_step() {
+            Some(step) => step.clone(),
+            None => return Ok(StakeStatus::Completed),
+        };
+        // Retrieve the staker's current bond and unbond state.
+        let staker = self.staker.to_string();
+        let (bond_state, unbond_state) = (get_bond_state(endpoint, &staker)?, get_unbond_state(endpoint, &staker)?);
+
+        // Authorize the step, once the previous step has been finalized.
+        let authorization = match step {
+            StakeStep::Unbond { microcredits } => Credits::unbond_public(
+                private_key,
+                microcredits,
+                priority_fee_in_microcredits,
+                ExecutionMode::Broadcast,
+                rng,
+            )?,
+            StakeStep::Claim => match (bond_state, unbond_state) {
+                (None, Some(unbond_state)) if get_latest_height(endpoint)? >= unbond_state.height => {
+                    Credits::claim_unbond_public(
+                        private_key,
+                        priority_fee_in_microcredits,
+                        ExecutionMode::Broadcast,
+                        rng,
+                    )?
+                }
+                _ => return Ok(StakeStatus::Waiting),
+            },
+            StakeStep::Bond { validator, microcredits } => match (bond_state, unbond_state) {
+                (None, None) => Credits::bond_public(
+                    private_key,
+                    &validator.to_string(),
+                    microcredits,
+                    priority_fee_in_microcredits,
+                    ExecutionMode::Broadcast,
+                    rng,
+                )?,
+                _ => return Ok(StakeStatus::Waiting),
+            },
+        };
+
+        // Record the step as pending before submitting it.
+        let transaction_id = authorization.transaction_id()?.to_string();
+        self.pending = Some(PendingStep { transaction_id, authorization: authorization.clone() });
+        self.persist()?;
+
+        // Execute the step.
+        match authorization.execute_with(&RetryPolicy::new(endpoint)) {
+            Ok(transaction) => self.record(transaction.id().to_string()),
+            // Discard the step if it was rejected, so that it is authorized again on the next call.
+            Err(error) if error.is::<RequestRejected>() => {
+                self.pending = None;
+                self.persist()?;
+                Err(error)
+            }
+            // Otherwise, keep the step pending, as it may have been accepted.
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Records the pending step as executed if its transaction is found on chain, or resubmits its authorization.
+    ///
+    /// If the resubmission fails, the step is kept pending, as the network also rejects a transaction it already has.
+    fn reconcile(&mut self, endpoint: &str, pending: PendingStep) -> Result<StakeStatus> {
+        let transaction_id = match pending.authorization.find_transaction_id(endpoint)? {
+            Some(transaction_id) => transaction_id,
+            None => pending.authorization.execute_with(&RetryPolicy::new(endpoint))?.id().to_string(),
+        };
+        self.record(transaction_id)
+    }
+
+    /// Records the pending step as executed with the given transaction ID, and persists the workflow.
+    fn record(&mut self, transaction_id: String) -> Result<StakeStatus> {
+        self.pending = None;
+        self.transaction_ids.push(transaction_id.clone());
+        self.persist()?;
+        Ok(StakeStatus::Executed(transaction_id))
+    }
+
+    /// Initializes a new workflow with the given steps, ensuring every step follows the protocol's minimum-bond rules.
+    fn new(staker: Address<N>, steps: Vec<StakeStep>, is_validator: bool) -> Result<Self> {
+        // Determine the minimum stake that the staker must keep bonded.
+        let (role, min_stake) = match is_validator {
+            true => ("validator", Microcredits::new(MIN_VALIDATOR_STAKE_IN_MICROCREDITS)),
+            false => ("delegator", Microcredits::new(MIN_DELEGATOR_STAKE_IN_MICROCREDITS)),
+        };
+        for step in &steps {
+            match step {
+                StakeStep::Unbond { microcredits } => ensure!(!microcredits.is_zero(), "There is no stake to unbond"),
+                StakeStep::Claim => (),
+                StakeStep::Bond { microcredits, .. } => {
+                    // Ensure the bond meets the minimum bond amount.
+                    ensure!(
+                        **microcredits >= MIN_BOND_AMOUNT_IN_MICROCREDITS,
+                        "The bond of {microcredits} is below the minimum bond amount of {}",
+                        Microcredits::new(MIN_BOND_AMOUNT_IN_MICROCREDITS)
+                    );
+                    // Ensure the bond meets the minimum stake of the staker.
+                    ensure!(
+                        *microcredits >= min_stake,
+                        "The stake of {microcredits} is below the minimum {role} stake of {min_stake}"
+                    );
+                }
+            }
+        }
+        Ok(Self { staker, steps, transaction_ids: vec![], pending: None, path: None })
+    }
+
+    /// Returns the bond state of the given staker, or an error if the staker is not bonded.
+    fn bond_state(endpoint: &str, staker: &str) -> Result<BondState> {
+        match get_bond_state(endpoint, staker)? {
+            Some(bond_state) => Ok(bond_state),
+            None => bail!("The address '{staker}' is not bonded"),
+        }
+    }
+
+    /// Persists the workflow to its path, if one is set.
+    fn persist(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            write_atomically(path, self)?;
+        }
+        Ok(())
+    }
+}
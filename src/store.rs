@@ -0,0 +1,242 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{file::write_atomically, get_base_fee_in_microcredits, Microcredits, OwnedRecord, N};
+use snarkvm::{
+    console::types::Field,
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+/// The status of a stored record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RecordStatus {
+    /// The record may be selected.
+    Unspent,
+    /// The record was selected by the given reservation, and is waiting to be spent or released.
+    Pending { reservation: String },
+    /// The record was spent on chain.
+    Spent,
+}
+
+/// A stored record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredRecord {
+    /// The owned record.
+    pub record: OwnedRecord,
+    /// The status of the record.
+    pub status: RecordStatus,
+}
+
+/// The records selected to pay a target amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// A single record covers the target amount.
+    Single(Box<OwnedRecord>),
+    /// The records only cover the target amount together, and must first be combined with `n - 1` calls to `join`.
+    Join(Vec<OwnedRecord>),
+}
+
+impl Selection {
+    /// Returns the selected records.
+    pub fn records(&self) -> &[OwnedRecord] {
+        match self {
+            Self::Single(record) => core::slice::from_ref(&**record),
+            Self::Join(records) => records,
+        }
+    }
+
+    /// Returns the total amount of the selected records.
+    pub fn microcredits(&self) -> Result<Microcredits> {
+        Microcredits::checked_sum(self.records().iter().map(OwnedRecord::microcredits).collect::<Result<Vec<_>>>()?)
+    }
+}
+
+/// A selection of records, which are pending until the reservation is confirmed or released.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reservation {
+    /// The ID of the reservation, which is the commitment of its first record.
+    pub id: String,
+    /// The selected records.
+    pub selection: Selection,
+}
+
+/// A local store of the `credits.aleo` records of an account, stored in a file.
+///
+/// Selecting records reserves them, so that two concurrent authorizations never spend the same record.
+/// A reservation is confirmed once its transaction is accepted, or released if it is abandoned.
+pub struct RecordStore {
+    /// The path to the store file.
+    path: PathBuf,
+    /// The stored records, keyed by commitment.
+    records: Mutex<BTreeMap<String, StoredRecord>>,
+}
+
+impl RecordStore {
+    /// Opens the store at the given path, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let records = match path.exists() {
+            true => serde_json::from_str(&fs::read_to_string(&path)?)?,
+            false => BTreeMap::new(),
+        };
+        Ok(Self { path, records: Mutex::new(records) })
+    }
+
+    /// Returns the stored record with the given commitment, if it exists.
+    pub fn get(&self, commitment: &Field<N>) -> Result<Option<StoredRecord>> {
+        Ok(self.lock()?.get(&commitment.to_string()).cloned())
+    }
+
+    /// Returns the stored records, in the order they were created.
+    pub fn records(&self) -> Result<Vec<StoredRecord>> {
+        let mut records = self.lock()?.values().cloned().collect::<Vec<_>>();
+        records.sort_by_key(|stored| stored.record.height());
+        Ok(records)
+    }
+
+    /// Returns the total amount of the unspent records, excluding any pending records.
+    pub fn balance(&self) -> Result<Microcredits> {
+        let records = self.lock()?;
+        Microcredits::checked_sum(
+            records
+                .values()
+                .filter(|stored| stored.status == RecordStatus::Unspent)
+                .map(|stored| stored.record.microcredits())
+                .collect::<Result<Vec<_>>>()?,
+        )
+    }
+
+    /// Synchronizes the store with the given unspent records, i.e. from `RecordScanner::unspent`.
+    ///
+    /// New records are added as unspent, and stored records that are no longer unspent are marked as spent.
+    pub fn sync(&self, unspent: &[OwnedRecord]) -> Result<()> {
+        let mut records = self.lock()?;
+        let commitments = unspent.iter().map(|record| record.commitment().to_string()).collect::<BTreeSet<_>>();
+
+        // Mark the records that are no longer unspent as spent.
+        for (commitment, stored) in records.iter_mut() {
+            if !commitments.contains(commitment) {
+                stored.status = RecordStatus::Spent;
+            }
+        }
+        // Add the new records.
+        for record in unspent {
+            records
+                .entry(record.commitment().to_string())
+                .or_insert_with(|| StoredRecord { record: record.clone(), status: RecordStatus::Unspent });
+        }
+        self.persist(&records)
+    }
+
+    /// Selects and reserves the records to pay the given amount and fee.
+    ///
+    /// The smallest record that covers the total is selected if one exists. Otherwise, the largest records are
+    /// selected until they cover the total and the base fees of the `n - 1` calls to `join` that combine them.
+    pub fn select(
+        &self,
+        amount_in_microcredits: impl Into<Microcredits>,
+        fee_in_microcredits: impl Into<Microcredits>,
+    ) -> Result<Reservation> {
+        let mut records = self.lock()?;
+
+        // Compute the total to cover.
+        let total = amount_in_microcredits.into().checked_add(fee_in_microcredits.into())?;
+        ensure!(!total.is_zero(), "The amount to select must be greater than zero");
+
+        // Sort the unspent records from largest to smallest.
+        let mut candidates = Vec::new();
+        for stored in records.values().filter(|stored| stored.status == RecordStatus::Unspent) {
+            candidates.push((stored.record.microcredits()?, &stored.record));
+        }
+        candidates.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        // Select the smallest record that covers the total, or the largest records that cover it together.
+        let selection = match candidates.iter().rev().find(|(microcredits, _)| *microcredits >= total) {
+            Some((_, record)) => Selection::Single(Box::new((*record).clone())),
+            None => {
+                // Each record after the first requires a call to `join`, whose base fee must also be covered.
+                let join_fee = Microcredits::new(get_base_fee_in_microcredits("credits.aleo", "join")?);
+                let mut selected = Vec::new();
+                let mut sum = Microcredits::ZERO;
+                let mut required = total;
+                for (microcredits, record) in &candidates {
+                    if sum >= required {
+                        break;
+                    }
+                    if !selected.is_empty() {
+                        required = required.checked_add(join_fee)?;
+                    }
+                    sum = sum.checked_add(*microcredits)?;
+                    selected.push((*record).clone());
+                }
+                if sum < required {
+                    bail!("Insufficient unspent records: {sum:#} is available, but {required:#} is required");
+                }
+                Selection::Join(selected)
+            }
+        };
+
+        // Reserve the selected records.
+        let id = selection.records()[0].commitment().to_string();
+        for record in selection.records() {
+            if let Some(stored) = records.get_mut(&record.commitment().to_string()) {
+                stored.status = RecordStatus::Pending { reservation: id.clone() };
+            }
+        }
+        self.persist(&records)?;
+        Ok(Reservation { id, selection })
+    }
+
+    /// Marks the records of the given reservation as spent, once its transaction is accepted.
+    pub fn confirm(&self, reservation: &str) -> Result<()> {
+        self.settle(reservation, RecordStatus::Spent)
+    }
+
+    /// Returns the records of the given reservation to the unspent records, i.e. if its authorization is abandoned.
+    pub fn release(&self, reservation: &str) -> Result<()> {
+        self.settle(reservation, RecordStatus::Unspent)
+    }
+
+    /// Sets the status of the records of the given reservation.
+    fn settle(&self, reservation: &str, status: RecordStatus) -> Result<()> {
+        let mut records = self.lock()?;
+        let pending = RecordStatus::Pending { reservation: reservation.to_string() };
+        let mut found = false;
+        for stored in records.values_mut().filter(|stored| stored.status == pending) {
+            stored.status = status.clone();
+            found = true;
+        }
+        ensure!(found, "The reservation '{reservation}' does not exist");
+        self.persist(&records)
+    }
+
+    /// Locks the stored records.
+    fn lock(&self) -> Result<MutexGuard<'_, BTreeMap<String, StoredRecord>>> {
+        self.records.lock().map_err(|_| anyhow!("The record store is poisoned"))
+    }
+
+    /// Persists the given records to the store file.
+    fn persist(&self, records: &BTreeMap<String, StoredRecord>) -> Result<()> {
+        write_atomically(&self.path, records)
+    }
+}
@@ -12,17 +12,66 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod test_abi;
+
+mod test_account;
+
+mod test_batch;
+
 mod test_bond_public;
 use test_bond_public::*;
 
 mod test_claim_unbond_public;
 use test_claim_unbond_public::*;
 
+#[cfg(feature = "cli")]
+mod test_cli;
+
 mod test_cost;
 
+mod test_derivation;
+
+mod test_envelope;
+
+mod test_estimator;
+
+mod test_file;
+
+mod test_journal;
+
+mod test_keystore;
+
+mod test_microcredits;
+
+mod test_mnemonic;
+
+mod test_mode;
+
+mod test_parallel;
+
+mod test_policy;
+
+mod test_predicted_ids;
+
+mod test_receipt;
+
+mod test_request;
+
+mod test_retry;
+
+mod test_scanner;
+
+mod test_secret;
+
 mod test_set_validator_state;
 use test_set_validator_state::*;
 
+mod test_staking;
+
+mod test_store;
+
+mod test_sweep_public;
+
 mod test_transfer_public;
 use test_transfer_public::*;
 
@@ -35,6 +84,9 @@ use test_unbond_delegator_as_validator::*;
 mod test_unbond_public;
 use test_unbond_public::*;
 
+mod test_watcher;
+
+use crate::Authorized;
 use snarkvm::{
     console::account::{Address, PrivateKey},
     prelude::TestRng,
@@ -43,6 +95,119 @@ use snarkvm::{
 type CurrentNetwork = snarkvm::console::network::Testnet3;
 type CurrentAleo = snarkvm::circuit::AleoV0;
 
+/// A request received by the mock server.
+struct MockRequest {
+    /// The request line, e.g. `POST /execute`.
+    request_line: String,
+    /// The request body.
+    body: String,
+}
+
+/// Serves the given responses in order on a local port, one per connection,
+/// returning the URL of the server and a handle to the requests it received.
+fn mock_server(responses: Vec<(u16, &str)>) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+    let mut responses = responses.into_iter().map(|(status, body)| (status, body.to_string())).collect::<Vec<_>>();
+    responses.reverse();
+    mock_server_with(responses.len(), move |_| responses.pop().unwrap())
+}
+
+/// Serves the given number of requests on a local port, one per connection, responding to each with the given function,
+/// and returning the URL of the server and a handle to the requests it received.
+fn mock_server_with(
+    num_requests: usize,
+    mut respond: impl FnMut(&MockRequest) -> (u16, String) + Send + 'static,
+) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+    mock_server_raw(num_requests, move |request| {
+        let (status, body) = respond(request);
+        format!(
+            "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    })
+}
+
+/// Serves the given number of requests on a local port, one per connection, responding to each with the raw
+/// HTTP response of the given function, and returning the URL of the server and a handle to the requests it received.
+fn mock_server_raw(
+    num_requests: usize,
+    mut respond: impl FnMut(&MockRequest) -> String + Send + 'static,
+) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..num_requests {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            // Read the request line, and the content length from the headers.
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            // Read the body.
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request_line = request_line.rsplit_once(' ').map_or(request_line.trim(), |(line, _)| line).to_string();
+            let request = MockRequest { request_line, body: String::from_utf8(body).unwrap() };
+            // Send the response, and close the connection.
+            stream.write_all(respond(&request).as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (url, handle)
+}
+
+/// The placeholder response to `POST /execute`, replaced with the transaction of the submitted authorization.
+const TRANSACTION: &str = "<transaction>";
+
+/// Serves the given responses in order, like `mock_server`,
+/// but replacing each `TRANSACTION` with the transaction of the submitted authorization.
+fn mock_node(mut responses: Vec<(u16, String)>) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+    responses.reverse();
+    mock_server_with(responses.len(), move |request| match responses.pop().unwrap() {
+        (status, body) if body == TRANSACTION => {
+            let transaction = authorization_of(request).execute_unproven().unwrap();
+            (status, serde_json::to_string(&transaction).unwrap())
+        }
+        response => response,
+    })
+}
+
+/// Returns the authorization submitted in the given request.
+fn authorization_of(request: &MockRequest) -> Authorized<CurrentNetwork> {
+    assert_eq!(request.request_line, "POST /execute");
+    serde_json::from_str(&request.body).unwrap()
+}
+
+/// Returns the `bonded` mapping value for the given validator and amount.
+fn bonded(validator: &Address<CurrentNetwork>, microcredits: u64) -> (u16, String) {
+    (200, format!("\"{{ validator: {validator}, microcredits: {microcredits}u64 }}\""))
+}
+
+/// Returns the `unbonding` mapping value for the given amount and height.
+fn unbonding(microcredits: u64, height: u32) -> (u16, String) {
+    (200, format!("\"{{ microcredits: {microcredits}u64, height: {height}u32 }}\""))
+}
+
+/// Returns a missing mapping value.
+fn none() -> (u16, String) {
+    (200, "null".to_string())
+}
+
 /// Samples a random private key and address.
 fn sample_account(rng: &mut TestRng) -> (PrivateKey<CurrentNetwork>, Address<CurrentNetwork>) {
     let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
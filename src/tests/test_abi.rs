@@ -0,0 +1,56 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{InputAbi, ProgramAbi, Visibility};
+
+#[test]
+fn test_credits_abi() {
+    let abi = ProgramAbi::credits().unwrap();
+    assert_eq!(abi.program_id, "credits.aleo");
+
+    // Ensure the functions describe their inputs.
+    let function = abi.function("transfer_public").unwrap();
+    assert_eq!(function.inputs, vec![
+        InputAbi { register: "r0".to_string(), type_name: "address".to_string(), visibility: Visibility::Public },
+        InputAbi { register: "r1".to_string(), type_name: "u64".to_string(), visibility: Visibility::Public },
+    ]);
+    let function = abi.function("transfer_private").unwrap();
+    assert_eq!(function.inputs[0].type_name, "credits");
+    assert_eq!(function.inputs[0].visibility, Visibility::Record);
+    assert_eq!(function.inputs[1].visibility, Visibility::Private);
+    assert!(abi.function("claim_unbond_public").unwrap().inputs.is_empty());
+    assert!(abi.function("mint").is_none());
+
+    // Ensure the mappings describe their types.
+    let mapping = abi.mapping("account").unwrap();
+    assert_eq!((mapping.key_type.as_str(), mapping.value_type.as_str()), ("address", "u64"));
+}
+
+#[test]
+fn test_parse_inputs() {
+    let rng = &mut TestRng::default();
+    let (_, address) = sample_account(rng);
+    let abi = ProgramAbi::credits().unwrap();
+
+    // Ensure valid inputs are parsed.
+    let inputs = abi.parse_inputs("transfer_public", &[address.to_string(), "5u64".to_string()]).unwrap();
+    assert_eq!(inputs[1].to_string(), "5u64");
+
+    // Ensure invalid inputs fail before they are authorized.
+    assert!(abi.parse_inputs("transfer_public", &[address.to_string()]).is_err());
+    assert!(abi.parse_inputs("transfer_public", &[address.to_string(), "5u32".to_string()]).is_err());
+    assert!(abi.parse_inputs("transfer_public", &["5u64", "5u64"]).is_err());
+    assert!(abi.parse_inputs("mint", &["5u64"]).is_err());
+}
@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Account, Credits, ExecutionMode, SecretString};
+use snarkvm::console::account::ViewKey;
+
+use core::str::FromStr;
+
+#[test]
+fn test_account() {
+    let rng = &mut TestRng::default();
+
+    // Sample an account.
+    let account = Account::new(rng).unwrap();
+    let private_key = PrivateKey::<CurrentNetwork>::from_str(account.private_key()).unwrap();
+    assert_eq!(*account.view_key(), ViewKey::try_from(&private_key).unwrap());
+    assert_eq!(account.address(), Address::try_from(&private_key).unwrap());
+
+    // Ensure the account is parsed from its private key.
+    let (private_key, address) = sample_account(rng);
+    let candidate = Account::from_str(&private_key.to_string()).unwrap();
+    assert_eq!(candidate.address(), address);
+    assert_eq!(*candidate.view_key(), ViewKey::try_from(&private_key).unwrap());
+    assert_eq!(candidate, Account::try_from(SecretString::from(private_key.to_string())).unwrap());
+    assert!(Account::from_str("APrivateKey1invalid").is_err());
+
+    // Ensure the private key is redacted when debugged.
+    assert_eq!(format!("{candidate:?}"), format!("Account({address})"));
+    assert_eq!(candidate.to_string(), address.to_string());
+
+    // Ensure the private key of the account is accepted by a `Credits` function.
+    assert!(Credits::claim_unbond_public(candidate.private_key(), 0, ExecutionMode::Prove, rng).is_ok());
+}
@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{get_base_fee_in_microcredits, ExecutionMode, Microcredits, Payout, Payouts};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Samples a batch of payouts to random recipients.
+fn sample_payouts(num_payouts: usize, rng: &mut TestRng) -> Payouts {
+    Payouts::new(
+        (0..num_payouts)
+            .map(|i| Payout {
+                recipient: sample_account(rng).1.to_string(),
+                amount_in_microcredits: Microcredits::new(1_000_000 * (i as u64 + 1)),
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_payouts_from_csv() {
+    let rng = &mut TestRng::default();
+
+    // Sample the recipients.
+    let (_, recipient_0) = sample_account(rng);
+    let (_, recipient_1) = sample_account(rng);
+
+    // Parse the CSV.
+    let csv = format!("recipient,amount\n{recipient_0}, 1.5 credits\n\n{recipient_1},1_000\n");
+    let payouts = Payouts::from_csv(&csv).unwrap();
+    assert_eq!(payouts.payouts(), &[
+        Payout { recipient: recipient_0.to_string(), amount_in_microcredits: Microcredits::new(1_500_000) },
+        Payout { recipient: recipient_1.to_string(), amount_in_microcredits: Microcredits::new(1_000) },
+    ]);
+
+    // Ensure malformed lines are rejected.
+    assert!(Payouts::from_csv(&recipient_0.to_string()).is_err());
+    assert!(Payouts::from_csv(&format!("{recipient_0},1,2")).is_err());
+    assert!(Payouts::from_csv(&format!("{recipient_0},1.5")).is_err());
+}
+
+#[test]
+fn test_payouts_validate() {
+    let rng = &mut TestRng::default();
+
+    // Ensure valid recipients are accepted.
+    let mut payouts = sample_payouts(3, rng).payouts().to_vec();
+    assert!(Payouts::new(payouts.clone()).validate().is_ok());
+
+    // Ensure every invalid recipient is reported.
+    payouts[0].recipient = "aleo1invalid".to_string();
+    payouts[2].recipient = "".to_string();
+    let error = Payouts::new(payouts).validate().unwrap_err().to_string();
+    assert!(error.contains("#0 'aleo1invalid'"));
+    assert!(error.contains("#2 ''"));
+}
+
+#[test]
+fn test_payouts_total_cost() {
+    let rng = &mut TestRng::default();
+
+    // Retrieve the base fee for `transfer_public`.
+    let base_fee = get_base_fee_in_microcredits("credits.aleo", "transfer_public").unwrap();
+
+    // Ensure the total cost includes the base fee and priority fee of every payout.
+    let payouts = sample_payouts(3, rng);
+    let expected = 6_000_000 + 3 * (base_fee + 1000);
+    assert_eq!(payouts.total_cost(1000).unwrap(), Microcredits::new(expected));
+
+    // Ensure the total cost cannot silently wrap.
+    let mut payouts = payouts.payouts().to_vec();
+    payouts[0].amount_in_microcredits = Microcredits::new(u64::MAX - base_fee);
+    assert!(Payouts::new(payouts).total_cost(0).is_err());
+}
+
+#[test]
+fn test_payouts_authorize_unchecked() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and payouts.
+    let (sender_private_key, _) = sample_account(rng);
+    let payouts = sample_payouts(4, rng);
+
+    // Authorize the payouts.
+    let progress = AtomicUsize::new(0);
+    let authorizations = payouts.authorize_unchecked(
+        &sender_private_key.to_string(),
+        Microcredits::new(1000),
+        ExecutionMode::Prove,
+        rng,
+        |_, result| {
+            assert!(result.is_ok());
+            progress.fetch_add(1, Ordering::SeqCst);
+        },
+    );
+
+    // Ensure every payout was authorized and reported.
+    assert_eq!(authorizations.len(), payouts.len());
+    assert!(authorizations.iter().all(|authorization| authorization.is_ok()));
+    assert_eq!(progress.load(Ordering::SeqCst), payouts.len());
+}
+
+#[test]
+fn test_payouts_authorize() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and payouts.
+    let (sender_private_key, sender_address) = sample_account(rng);
+    let payouts = sample_payouts(2, rng);
+    let total_cost = payouts.total_cost(1000).unwrap();
+
+    // Serve a balance that covers the total cost, followed by one that does not.
+    let (sufficient, insufficient) = (format!("\"{}u64\"", *total_cost), format!("\"{}u64\"", *total_cost - 1));
+    let (url, server) = mock_server(vec![(200, &sufficient), (200, &insufficient)]);
+    let authorize = |rng: &mut TestRng| {
+        payouts.authorize(&url, &sender_private_key.to_string(), 1000u64, ExecutionMode::Prove, rng, |_, _| ())
+    };
+
+    // Ensure the payouts are authorized if the balance at the endpoint covers the total cost.
+    let authorizations = authorize(rng).unwrap();
+    assert!(authorizations.iter().all(|authorization| authorization.is_ok()));
+    // Ensure the payouts are not authorized otherwise.
+    assert!(authorize(rng).err().unwrap().to_string().contains("insufficient"));
+
+    // Ensure the balance of the sender was read from the endpoint.
+    let requests = server.join().unwrap();
+    assert!(requests
+        .iter()
+        .all(|request| request.request_line == format!("GET /program/credits.aleo/mapping/account/{sender_address}")));
+}
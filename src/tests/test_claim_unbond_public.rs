@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::{Authorized, Credits};
+use crate::{Authorized, Credits, ExecutionMode};
 
 use rand::Rng;
 
@@ -25,7 +25,13 @@ pub(crate) fn sample_claim_unbond_public(rng: &mut TestRng) -> Authorized<Curren
     let priority_fee_in_microcredits = rng.gen_range(1..1000000);
 
     // Sample the authorization.
-    Credits::claim_unbond_public(&staker_private_key.to_string(), priority_fee_in_microcredits, false, rng).unwrap()
+    Credits::claim_unbond_public(
+        &staker_private_key.to_string(),
+        priority_fee_in_microcredits,
+        ExecutionMode::Prove,
+        rng,
+    )
+    .unwrap()
 }
 
 #[test]
@@ -0,0 +1,148 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Authorized, Cli};
+
+use clap::Parser;
+
+/// Writes the given private key to a temporary file, returning its path.
+fn write_private_key(private_key: &PrivateKey<CurrentNetwork>) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("cli_{}.key", Address::try_from(private_key).unwrap()));
+    std::fs::write(&path, format!("{private_key}\n")).unwrap();
+    path
+}
+
+#[test]
+fn test_cli_parse() {
+    // Ensure every subcommand parses.
+    let address = "aleo1zeklp6dd8e764spe74xez6f8w27dlua3w7hl4z2uln03re52egpsv46ngg";
+    for args in [
+        vec!["bond-public", address, "10 credits"],
+        vec!["unbond-public", "1_000_000"],
+        vec!["unbond-delegator-as-validator", address],
+        vec!["claim-unbond-public"],
+        vec!["set-validator-state", "false"],
+        vec!["transfer-public", address, "1.5 credits"],
+        vec!["transfer-public-to-private", address, "1500000"],
+        vec!["authorize", "transfer-public", address, "1", "--out", "authorization.json"],
+    ] {
+        let args = ["credits", "--dry-run", "--output", "json"].into_iter().chain(args.clone());
+        assert!(Cli::try_parse_from(args).is_ok());
+    }
+
+    // Ensure invalid amounts are rejected.
+    assert!(Cli::try_parse_from(["credits", "unbond-public", "1.5"]).is_err());
+    assert!(Cli::try_parse_from(["credits", "transfer-public", address]).is_err());
+}
+
+#[test]
+fn test_cli_dry_run() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and recipient.
+    let (private_key, _) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+    let path = write_private_key(&private_key);
+
+    // Run a dry run of a transfer.
+    let cli = Cli::try_parse_from([
+        "credits",
+        "transfer-public",
+        &recipient.to_string(),
+        "1 credit",
+        "--priority-fee",
+        "1000",
+        "--private-key-file",
+        path.to_str().unwrap(),
+        "--output",
+        "json",
+        "--dry-run",
+    ])
+    .unwrap();
+    let summary: serde_json::Value = serde_json::from_str(&cli.run().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Ensure the summary is correct.
+    assert_eq!(summary["function"], "transfer_public");
+    assert_eq!(summary["base_fee"], 263388);
+    assert_eq!(summary["priority_fee"], 1000);
+}
+
+#[test]
+fn test_cli_authorize() {
+    let rng = &mut TestRng::default();
+
+    // Sample the staker.
+    let (private_key, _) = sample_account(rng);
+    let path = write_private_key(&private_key);
+    let out = path.with_extension("json");
+
+    // Authorize a claim, writing the authorization to a file.
+    let cli = Cli::try_parse_from([
+        "credits",
+        "authorize",
+        "claim-unbond-public",
+        "--private-key-file",
+        path.to_str().unwrap(),
+        "--out",
+        out.to_str().unwrap(),
+    ])
+    .unwrap();
+    cli.run().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Ensure the authorization is written.
+    let authorization = std::fs::read_to_string(&out).unwrap();
+    std::fs::remove_file(&out).unwrap();
+    let authorization: Authorized<CurrentNetwork> = serde_json::from_str(&authorization).unwrap();
+    assert_eq!(
+        serde_json::to_value(authorization).unwrap()["function"]["requests"][0]["function"],
+        "claim_unbond_public"
+    );
+}
+
+#[test]
+fn test_cli_keystore() {
+    let rng = &mut TestRng::default();
+
+    // Import the staker into a keystore.
+    let (private_key, address) = sample_account(rng);
+    let directory = std::env::temp_dir().join(format!("cli_keystore_{address}"));
+    crate::Keystore::open(&directory).unwrap().import(&private_key.to_string(), "password", rng).unwrap();
+
+    // Run a dry run of a claim, unlocking the private key from the keystore.
+    let password_env = format!("CREDITS_TEST_PASSWORD_{address}");
+    std::env::set_var(&password_env, "password");
+    let cli = Cli::try_parse_from([
+        "credits",
+        "claim-unbond-public",
+        "--keystore",
+        directory.to_str().unwrap(),
+        "--address",
+        &address.to_string(),
+        "--password-env",
+        &password_env,
+        "--output",
+        "json",
+        "--dry-run",
+    ])
+    .unwrap();
+    let summary: serde_json::Value = serde_json::from_str(&cli.run().unwrap()).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(summary["function"], "claim_unbond_public");
+
+    // Ensure the keystore requires an address.
+    assert!(Cli::try_parse_from(["credits", "claim-unbond-public", "--keystore", "keystore"]).is_err());
+}
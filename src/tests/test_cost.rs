@@ -70,3 +70,34 @@ fn test_cost() {
     // Print the string.
     println!("\n{string}");
 }
+
+#[test]
+fn test_cost_join() {
+    use snarkvm::{
+        console::{network::Network, types::Scalar},
+        ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        prelude::Uniform,
+        synthesizer::VM,
+    };
+
+    let rng = &mut TestRng::default();
+
+    // Initialize the VM.
+    let vm = VM::from(ConsensusStore::<_, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+
+    // Sample two records of the same owner.
+    let (private_key, address) = sample_account(rng);
+    let records = [1_000_000u64, 2_000_000].map(|amount| {
+        let nonce = CurrentNetwork::g_scalar_multiply(&Scalar::rand(rng));
+        format!("{{ owner: {address}.private, microcredits: {amount}u64.private, _nonce: {nonce}.public }}")
+    });
+
+    // Authorize the join, as it is not exposed as a `Credits` function.
+    let function =
+        crate::PROCESS.authorize::<CurrentAleo, _>(&private_key, "credits.aleo", "join", records.iter(), rng).unwrap();
+    let transaction = Authorized::new(function, None, crate::ExecutionMode::Prove).execute_unproven().unwrap();
+
+    // Ensure the base fee of the join is the cost of its execution.
+    let (cost, _) = snarkvm::synthesizer::execution_cost(&vm, transaction.execution().unwrap()).unwrap();
+    assert_eq!(cost, crate::config::get_base_fee_in_microcredits("credits.aleo", "join").unwrap());
+}
@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Credits, ExecutionMode, MasterSeed};
+
+use core::str::FromStr;
+
+/// The master seed of the test vectors.
+const SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+fn test_derivation_vectors() {
+    let seed = MasterSeed::from_hex(SEED).unwrap();
+
+    // Ensure the derivation matches the test vectors.
+    for (index, private_key, address) in [
+        (
+            0,
+            "APrivateKey1zkpJ877vKgGh5pRReReAu4gGrkfKWeuq9yxoMhRkTbXCBbE",
+            "aleo156644tad98v27f222qh72wj9wa5tls63f7kwlhxzqt0vgvcmdggs4tzv2a",
+        ),
+        (
+            1,
+            "APrivateKey1zkpCpr8LQwd5atmaJg5UTXsXRgEaW11cVkL1coS9cuAQ9RD",
+            "aleo1w0kqvwjkvd4ydd7cucxa7u5nwt8lzy5m6z8d6x4kxl4t70vnwvxsafhz59",
+        ),
+        (
+            u32::MAX,
+            "APrivateKey1zkpDTeJbkdTq3JYKPSnJrA2ExwXtMJVtkCrxtWp5fFYDzJa",
+            "aleo1mznf8kynftx2ttgyjwzt86emt9mcqn6cnqlgr7m908spaez0ccqqqeupzl",
+        ),
+    ] {
+        assert_eq!(seed.private_key(index).unwrap().expose(), private_key);
+        assert_eq!(seed.address(index).unwrap().to_string(), address);
+    }
+
+    // Ensure a 64-byte master seed matches the test vector.
+    let seed = MasterSeed::from_bytes(&[0xff; 64]).unwrap();
+    assert_eq!(seed.private_key(0).unwrap().expose(), "APrivateKey1zkp7rrBLugfrRZuPcETPrGkVprQWeRTkrR2PBMK3hMacwsP");
+    assert_eq!(seed.address(0).unwrap().to_string(), "aleo1g2qx6clg5lda0g6yqmntdrre3qjzznnukf528muqqvcvs2kx0gpscdrt3g");
+}
+
+#[test]
+fn test_derivation() {
+    let rng = &mut TestRng::default();
+
+    // Ensure the same master seed always yields the same accounts, and different indices yield different accounts.
+    let seed = MasterSeed::new(rng);
+    let recovered = MasterSeed::from_hex(seed.to_hex().expose()).unwrap();
+    assert_eq!(seed.address(7).unwrap(), recovered.address(7).unwrap());
+    assert_ne!(seed.address(7).unwrap(), seed.address(8).unwrap());
+    // Ensure different master seeds yield different accounts.
+    assert_ne!(seed.address(0).unwrap(), MasterSeed::new(rng).address(0).unwrap());
+
+    // Ensure the private key matches the address.
+    let private_key = seed.private_key(3).unwrap();
+    let address = Address::try_from(PrivateKey::<CurrentNetwork>::from_str(&private_key).unwrap()).unwrap();
+    assert_eq!(address, seed.address(3).unwrap());
+
+    // Ensure the index of an address is found.
+    assert_eq!(seed.find(&address, 0..10).unwrap(), Some(3));
+    assert_eq!(seed.find(&address, 4..10).unwrap(), None);
+
+    // Ensure the private key is accepted by a `Credits` function.
+    assert!(Credits::claim_unbond_public(&private_key, 0, ExecutionMode::Prove, rng).is_ok());
+
+    // Ensure the master seed size is checked.
+    assert!(MasterSeed::from_bytes(&[0; 31]).is_err());
+    assert!(MasterSeed::from_bytes(&[0; 65]).is_err());
+    assert_eq!(format!("{seed:?}"), "MasterSeed(***)");
+}
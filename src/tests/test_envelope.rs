@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Authorized, ENVELOPE_MAGIC, ENVELOPE_VERSION};
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use sha2::{Digest, Sha256};
+
+#[test]
+fn test_bytes() {
+    let rng = &mut TestRng::default();
+
+    // Ensure the authorization round-trips through bytes.
+    let authorization = sample_transfer_public(rng);
+    let bytes = authorization.to_bytes_le().unwrap();
+    let candidate = Authorized::<CurrentNetwork>::from_bytes_le(&bytes).unwrap();
+    assert_eq!(candidate.execution_id().unwrap(), authorization.execution_id().unwrap());
+    assert_eq!(candidate.to_bytes_le().unwrap(), bytes);
+    assert_eq!(serde_json::to_string(&candidate).unwrap(), serde_json::to_string(&authorization).unwrap());
+
+    // Ensure the bytes are more compact than JSON.
+    assert!(bytes.len() < serde_json::to_string(&authorization).unwrap().len());
+}
+
+#[test]
+fn test_envelope() {
+    let rng = &mut TestRng::default();
+
+    let authorization = sample_transfer_public(rng);
+    let execution_id = authorization.execution_id().unwrap();
+
+    // Ensure the envelope has the expected header.
+    let envelope = authorization.to_envelope().unwrap();
+    assert_eq!(envelope[0..4], ENVELOPE_MAGIC);
+    assert_eq!(envelope[4], ENVELOPE_VERSION);
+    assert_eq!(u16::from_le_bytes([envelope[5], envelope[6]]), 3);
+
+    // Ensure the authorization round-trips through the envelope, base64, and QR parts.
+    let candidate = Authorized::<CurrentNetwork>::from_envelope(&envelope).unwrap();
+    assert_eq!(candidate.execution_id().unwrap(), execution_id);
+    let candidate = Authorized::<CurrentNetwork>::from_base64(&authorization.to_base64().unwrap()).unwrap();
+    assert_eq!(candidate.execution_id().unwrap(), execution_id);
+    let mut parts = authorization.to_qr_parts(1000).unwrap();
+    assert!(parts.len() > 1);
+    assert!(parts.iter().all(|part| part.len() <= 1000));
+    assert!(parts.iter().flat_map(|part| part.chars()).all(|c| c.is_ascii_uppercase() || "0123456789/:".contains(c)));
+    parts.reverse();
+    let candidate = Authorized::<CurrentNetwork>::from_qr_parts(&parts).unwrap();
+    assert_eq!(candidate.execution_id().unwrap(), execution_id);
+
+    // Ensure missing or duplicate QR parts are rejected.
+    assert!(Authorized::<CurrentNetwork>::from_qr_parts(&parts[1..]).is_err());
+    let duplicates = vec![parts[0].clone(); parts.len()];
+    assert!(Authorized::<CurrentNetwork>::from_qr_parts(&duplicates).is_err());
+
+    // Ensure a corrupted envelope is rejected.
+    let mut corrupted = envelope.clone();
+    corrupted[20] ^= 1;
+    let error = Authorized::<CurrentNetwork>::from_envelope(&corrupted).err().unwrap();
+    assert!(error.to_string().contains("checksum"));
+
+    // Ensure an envelope for another network is rejected, even with a valid checksum.
+    let mut other = envelope[..envelope.len() - 4].to_vec();
+    other[5..7].copy_from_slice(&1u16.to_le_bytes());
+    let checksum = Sha256::digest(&other);
+    other.extend_from_slice(&checksum[..4]);
+    let error = Authorized::<CurrentNetwork>::from_envelope(&other).err().unwrap();
+    assert!(error.to_string().contains("network 1"));
+}
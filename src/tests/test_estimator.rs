@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{CreditsCall, ExecutionMode, FeeEstimate, FeeEstimator, FeeLevel, Microcredits};
+use snarkvm::{ledger::block::Block, prelude::FromBytes};
+
+#[test]
+fn test_fee_estimate() {
+    // Ensure an empty sample suggests no priority fee.
+    assert_eq!(FeeEstimate::from_priority_fees(vec![]), FeeEstimate::default());
+
+    // Ensure a single sample is suggested at every level.
+    let estimate = FeeEstimate::from_priority_fees(vec![5000]);
+    assert_eq!(estimate, FeeEstimate { low: 5000, medium: 5000, high: 5000 });
+
+    // Ensure the percentiles are computed over the sorted sample.
+    let estimate = FeeEstimate::from_priority_fees((1..=100).rev().collect());
+    assert_eq!(estimate.get(FeeLevel::Low), 25);
+    assert_eq!(estimate.get(FeeLevel::Medium), 50);
+    assert_eq!(estimate.get(FeeLevel::High), 90);
+}
+
+#[test]
+fn test_fee_estimator_remote() {
+    // Estimate the priority fees.
+    let estimate = FeeEstimator::default().estimate().unwrap();
+    println!("{estimate:?}");
+
+    // Ensure the fee levels are ordered.
+    assert!(estimate.low <= estimate.medium);
+    assert!(estimate.medium <= estimate.high);
+}
+
+#[test]
+fn test_authorize_with_fee_level() {
+    let rng = &mut TestRng::default();
+
+    // Serve the genesis block as the only recent block.
+    let block =
+        Block::<CurrentNetwork>::from_bytes_le(snarkvm::parameters::testnet3::GenesisBytes::load_bytes()).unwrap();
+    let expected = block
+        .transactions()
+        .iter()
+        .filter_map(|confirmed| confirmed.transaction().fee_transition())
+        .map(|fee| *fee.priority_amount().unwrap())
+        .collect::<Vec<_>>();
+    let expected = FeeEstimate::from_priority_fees(expected).get(FeeLevel::High);
+    let (url, server) = mock_server(vec![(200, "0"), (200, &serde_json::to_string(&block).unwrap())]);
+
+    // Authorize a transfer with the suggested priority fee.
+    let (private_key, address) = sample_account(rng);
+    let call =
+        CreditsCall::TransferPublic { recipient: address.to_string(), amount_in_microcredits: Microcredits::new(1) };
+    let authorization = call
+        .authorize_with_fee_level(
+            &private_key.to_string(),
+            &FeeEstimator::new(&url, 1),
+            FeeLevel::High,
+            ExecutionMode::Prove,
+            rng,
+        )
+        .unwrap();
+
+    // Ensure the priority fee was estimated from the recent blocks.
+    assert_eq!(authorization.priority_fee().unwrap(), Microcredits::new(expected));
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].request_line, "GET /latest/height");
+    assert_eq!(requests[1].request_line, "GET /block/0");
+}
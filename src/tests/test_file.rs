@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::file::write_atomically;
+
+#[test]
+fn test_write_atomically() {
+    let rng = &mut TestRng::default();
+    let (_, address) = sample_account(rng);
+
+    // Write two files that share a stem into a new directory.
+    let directory = std::env::temp_dir().join(format!("write_atomically_{address}"));
+    std::fs::create_dir(&directory).unwrap();
+    write_atomically(directory.join("state.json"), &vec![1, 2, 3]).unwrap();
+    write_atomically(directory.join("state.keys"), &"keys").unwrap();
+    // Overwrite the first file.
+    write_atomically(directory.join("state.json"), &vec![4, 5]).unwrap();
+
+    // Ensure both files hold their latest value, and no temporary file is left behind.
+    let read = |name: &str| std::fs::read_to_string(directory.join(name)).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<u32>>(&read("state.json")).unwrap(), vec![4, 5]);
+    assert_eq!(serde_json::from_str::<String>(&read("state.keys")).unwrap(), "keys");
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 2);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Credits, ExecutionMode, Journal, JournalStatus, RetryPolicy, API_URL};
+
+use std::time::Duration;
+
+#[test]
+fn test_journal_record() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and recipient.
+    let (private_key, _) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+
+    // Authorize a transfer.
+    let authorization = Credits::transfer_public(
+        &private_key.to_string(),
+        &recipient.to_string(),
+        1_000_000,
+        1000,
+        ExecutionMode::Prove,
+        rng,
+    )
+    .unwrap();
+
+    // Record the authorization.
+    let path = std::env::temp_dir().join(format!("journal_{recipient}.json"));
+    let mut journal = Journal::open(&path).unwrap();
+    let execution_id = journal.record(&authorization).unwrap();
+    assert_eq!(execution_id, authorization.execution_id().unwrap().to_string());
+    // Ensure recording the authorization again keeps the existing entry.
+    assert_eq!(journal.record(&authorization).unwrap(), execution_id);
+    assert_eq!(journal.entries().count(), 1);
+
+    // Ensure a recorded authorization is not executed again.
+    assert!(journal.execute(authorization.clone(), &RetryPolicy::new(API_URL)).is_err());
+
+    // Reopen the journal.
+    let candidate = Journal::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Ensure the entry is recovered as pending, with the same authorization.
+    let (candidate_id, entry) = candidate.pending().next().unwrap();
+    assert_eq!(candidate_id, &execution_id);
+    assert_eq!(entry.status, JournalStatus::Pending);
+    assert_eq!(serde_json::to_value(&entry.authorization).unwrap(), serde_json::to_value(&authorization).unwrap());
+}
+
+#[test]
+fn test_journal_reconcile() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and recipient.
+    let (private_key, _) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+
+    // Authorize two transfers.
+    let mut authorize = |amount| {
+        Credits::transfer_public(
+            &private_key.to_string(),
+            &recipient.to_string(),
+            amount,
+            1000,
+            ExecutionMode::Prove,
+            rng,
+        )
+        .unwrap()
+    };
+    let (confirmed, resubmitted) = (authorize(1_000_000), authorize(2_000_000));
+
+    // Record the first authorization.
+    let path = std::env::temp_dir().join(format!("journal_reconcile_{recipient}.json"));
+    let mut journal = Journal::open(&path).unwrap();
+    let confirmed_id = journal.record(&confirmed).unwrap();
+
+    // Reconcile the journal, with the first authorization found on chain.
+    let transaction_id = confirmed.transaction_id().unwrap().to_string();
+    let (url, server) = mock_server(vec![(200, &format!("\"{transaction_id}\""))]);
+    let policy = RetryPolicy::new(&url).with_backoff(Duration::ZERO, Duration::ZERO);
+    journal.reconcile(&policy).unwrap();
+
+    // Ensure the transaction was found by the first transition ID, and the entry is confirmed.
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].request_line, format!("GET /find/transactionID/{}", confirmed.transition_ids()[0]));
+    assert_eq!(journal.get(&confirmed_id).unwrap().status, JournalStatus::Confirmed { transaction_id });
+
+    // Record the second authorization.
+    let resubmitted_id = journal.record(&resubmitted).unwrap();
+
+    // Reconcile the journal, with the second authorization not found on chain, and its resubmission rejected.
+    let (url, server) = mock_server(vec![(404, ""), (400, "\"The transaction was rejected\"")]);
+    let policy = RetryPolicy::new(&url).with_backoff(Duration::ZERO, Duration::ZERO);
+    assert!(journal.reconcile(&policy).is_err());
+
+    // Ensure only the second authorization was looked up, and then resubmitted as is.
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].request_line, format!("GET /find/transactionID/{}", resubmitted.transition_ids()[0]));
+    assert_eq!(requests[1].request_line, "POST /execute");
+    assert_eq!(requests[1].body, serde_json::to_string(&resubmitted).unwrap());
+
+    // Ensure the second entry is still pending.
+    let candidate = Journal::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(candidate.get(&resubmitted_id).unwrap().status, JournalStatus::Pending);
+    assert!(matches!(candidate.get(&confirmed_id).unwrap().status, JournalStatus::Confirmed { .. }));
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Keystore, KeystoreEntry, KEYSTORE_VERSION};
+
+#[test]
+fn test_keystore_entry() {
+    let rng = &mut TestRng::default();
+
+    // Encrypt a private key.
+    let (private_key, address) = sample_account(rng);
+    let entry = KeystoreEntry::encrypt(&private_key, "password", rng).unwrap();
+    assert_eq!(entry.address().unwrap(), address);
+
+    // Ensure the file format is versioned, and does not contain the private key.
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["version"], KEYSTORE_VERSION);
+    assert_eq!(json["kdf"]["function"], "argon2id");
+    assert_eq!(json["cipher"]["function"], "xchacha20poly1305");
+    assert!(!json.to_string().contains(&private_key.to_string()));
+
+    // Ensure the private key is only decrypted with the correct password.
+    assert_eq!(*entry.decrypt("password").unwrap(), private_key);
+    assert!(entry.decrypt("incorrect").is_err());
+
+    // Ensure the address is authenticated.
+    let (_, other) = sample_account(rng);
+    let mut json = json;
+    json["address"] = serde_json::Value::String(other.to_string());
+    let tampered: KeystoreEntry = serde_json::from_value(json).unwrap();
+    assert!(tampered.decrypt("password").is_err());
+}
+
+#[test]
+fn test_keystore() {
+    let rng = &mut TestRng::default();
+
+    // Open a new keystore.
+    let (first, first_address) = sample_account(rng);
+    let (second, second_address) = sample_account(rng);
+    let directory = std::env::temp_dir().join(format!("keystore_{first_address}"));
+    let keystore = Keystore::open(&directory).unwrap();
+
+    // Import the private keys.
+    assert_eq!(keystore.import(&first.to_string(), "first", rng).unwrap(), first_address);
+    assert_eq!(keystore.import(&second.to_string(), "second", rng).unwrap(), second_address);
+    // Ensure a private key is not imported twice.
+    assert!(keystore.import(&first.to_string(), "first", rng).is_err());
+
+    // Ensure the addresses are listed in sorted order.
+    let mut expected = vec![first_address, second_address];
+    expected.sort_by_key(|address| address.to_string());
+    assert_eq!(keystore.list().unwrap(), expected);
+
+    // Unlock the private keys.
+    assert_eq!(keystore.unlock(&first_address, "first").unwrap().expose(), first.to_string());
+    assert_eq!(keystore.unlock(&second_address, "second").unwrap().expose(), second.to_string());
+    assert!(keystore.unlock(&first_address, "second").is_err());
+
+    // Ensure only the owner can read the keystore files.
+    #[cfg(unix)]
+    for entry in std::fs::read_dir(&directory).unwrap() {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = entry.unwrap().metadata().unwrap();
+        if metadata.is_file() {
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        }
+    }
+
+    // Export an entry into another keystore.
+    let other = Keystore::open(directory.join("other")).unwrap();
+    other.import_entry(keystore.export(&first_address).unwrap()).unwrap();
+    assert_eq!(other.list().unwrap(), vec![first_address]);
+    assert_eq!(other.unlock(&first_address, "first").unwrap().expose(), first.to_string());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
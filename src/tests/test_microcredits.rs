@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{get_total_cost_in_microcredits, Microcredits, Unit};
+
+use core::str::FromStr;
+
+#[test]
+fn test_microcredits_from_str() {
+    // Ensure the supported formats parse to the same amount.
+    let expected = Microcredits::new(1_500_000);
+    for string in ["1.5 credits", "1.5credits", "1.500000 credits", "1500000 microcredits", "1_500_000", " 1500000 "] {
+        assert_eq!(Microcredits::from_str(string).unwrap(), expected, "Failed to parse '{string}'");
+    }
+    assert_eq!(Microcredits::from_str("1 credit").unwrap(), Microcredits::new(1_000_000));
+    assert_eq!(Microcredits::from_str(".000001 credits").unwrap(), Microcredits::new(1));
+
+    // Ensure ambiguous or malformed amounts are rejected.
+    for string in ["1.5", "", "credits", ". credits", "1.0000001 credits", "-1", "1e6", "1.5 aleo", "1.2.3 credits"] {
+        assert!(Microcredits::from_str(string).is_err(), "Parsed '{string}'");
+    }
+
+    // Ensure overflowing amounts are rejected.
+    assert!(Microcredits::from_str("18446744073709551616").is_err());
+    assert!(Microcredits::from_str("18446744073710 credits").is_err());
+}
+
+#[test]
+fn test_microcredits_display() {
+    let amount = Microcredits::new(1_500_000);
+    assert_eq!(amount.to_string(), "1500000 microcredits");
+    assert_eq!(format!("{amount:#}"), "1.5 credits");
+    assert_eq!(amount.to_string_in(Unit::Credits), "1.5 credits");
+    assert_eq!(Microcredits::new(2_000_000).to_string_in(Unit::Credits), "2 credits");
+    assert_eq!(Microcredits::new(1).to_string_in(Unit::Credits), "0.000001 credits");
+
+    // Ensure the displayed amounts parse back to the same amount.
+    for amount in [0, 1, 999_999, 1_000_000, 1_234_567, u64::MAX].map(Microcredits::new) {
+        assert_eq!(Microcredits::from_str(&amount.to_string_in(Unit::Credits)).unwrap(), amount);
+        assert_eq!(Microcredits::from_str(&amount.to_string_in(Unit::Microcredits)).unwrap(), amount);
+    }
+}
+
+#[test]
+fn test_microcredits_checked_arithmetic() {
+    let (one, max) = (Microcredits::new(1), Microcredits::new(u64::MAX));
+
+    assert_eq!(one.checked_add(one).unwrap(), Microcredits::new(2));
+    assert!(max.checked_add(one).is_err());
+    assert!(Microcredits::ZERO.checked_sub(one).is_err());
+    assert!(max.checked_mul(2).is_err());
+    assert!(Microcredits::checked_sum([max, one]).is_err());
+    assert!(Microcredits::from_credits(u64::MAX).is_err());
+
+    // Ensure the total cost of a transaction cannot silently wrap.
+    let amount = Microcredits::new(u64::MAX - 1);
+    assert!(get_total_cost_in_microcredits("credits.aleo", "transfer_public", amount, one).is_err());
+}
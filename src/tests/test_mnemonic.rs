@@ -0,0 +1,86 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::Mnemonic;
+
+/// The phrase of the first BIP39 test vector.
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+#[test]
+fn test_mnemonic_vectors() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE).unwrap();
+    assert_eq!(mnemonic.word_count(), 12);
+
+    // Ensure the master seed matches the BIP39 test vector.
+    assert_eq!(
+        mnemonic.to_master_seed("TREZOR").unwrap().to_hex().expose(),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+
+    // Ensure the derived accounts match the test vectors.
+    for (passphrase, private_key, address) in [
+        (
+            "TREZOR",
+            "APrivateKey1zkp5WfXYKexepo3jZoN1BMwh2kUw4ZxptXsWopnA6Z5YBDC",
+            "aleo150hncm5nplczd09laxesfrvya5aa4w08drkzh3ljcxs6tmnz4g9ssqe7vn",
+        ),
+        (
+            "",
+            "APrivateKey1zkp9kNvVPxghxkyowYER5v4afMeGxwL4TaNg7ckZhniAJM4",
+            "aleo1zdadr6kfygc6azkv7ndpljea9whd870yg46kyv7t0usywpfe6vyq9he3h7",
+        ),
+    ] {
+        assert_eq!(mnemonic.private_key(passphrase, 0).unwrap().expose(), private_key);
+        assert_eq!(mnemonic.to_master_seed(passphrase).unwrap().address(0).unwrap().to_string(), address);
+    }
+}
+
+#[test]
+fn test_mnemonic_from_phrase() {
+    // Ensure the phrase is normalized.
+    let mnemonic = Mnemonic::from_phrase(&format!("  {}  ", PHRASE.replace(' ', "   "))).unwrap();
+    assert_eq!(mnemonic.phrase(), PHRASE);
+
+    // Ensure an invalid checksum is rejected.
+    assert!(Mnemonic::from_phrase(&PHRASE.replace("about", "abandon")).is_err());
+    // Ensure an unknown word is rejected.
+    assert!(Mnemonic::from_phrase(&PHRASE.replace("about", "aleo")).is_err());
+    // Ensure an invalid number of words is rejected.
+    assert!(Mnemonic::from_phrase("abandon abandon about").is_err());
+    // Ensure the phrase is redacted when debugged.
+    assert_eq!(format!("{mnemonic:?}"), "Mnemonic(***)");
+}
+
+#[test]
+fn test_mnemonic_new() {
+    let rng = &mut TestRng::default();
+
+    for word_count in Mnemonic::WORD_COUNTS {
+        // Generate a new phrase.
+        let mnemonic = Mnemonic::new(word_count, rng).unwrap();
+        assert_eq!(mnemonic.word_count(), word_count);
+
+        // Ensure the phrase restores the same accounts.
+        let restored = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+        assert_eq!(restored, mnemonic);
+        assert_eq!(
+            restored.to_master_seed("").unwrap().address(5).unwrap(),
+            mnemonic.to_master_seed("").unwrap().address(5).unwrap()
+        );
+    }
+
+    // Ensure an unsupported number of words is rejected.
+    assert!(Mnemonic::new(13, rng).is_err());
+}
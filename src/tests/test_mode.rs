@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Authorized, Credits, ExecutionMode, Microcredits};
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+#[test]
+fn test_execution_mode() {
+    let rng = &mut TestRng::default();
+
+    let (private_key, _) = sample_account(rng);
+    let private_key = private_key.to_string();
+
+    for mode in [ExecutionMode::Prove, ExecutionMode::Broadcast, ExecutionMode::DryRun] {
+        let authorization = Credits::claim_unbond_public(&private_key, 0, mode, rng).unwrap();
+        assert_eq!(authorization.mode(), mode);
+
+        // Ensure the execution API receives the broadcast flag.
+        let json = serde_json::to_value(&authorization).unwrap();
+        assert_eq!(json["broadcast"], mode.is_broadcast());
+        assert_eq!(json.get("dry_run").is_some(), mode.is_dry_run());
+
+        // Ensure the mode round-trips through JSON and bytes.
+        let candidate: Authorized<CurrentNetwork> = serde_json::from_value(json).unwrap();
+        assert_eq!(candidate.mode(), mode);
+        let candidate = Authorized::<CurrentNetwork>::from_bytes_le(&authorization.to_bytes_le().unwrap()).unwrap();
+        assert_eq!(candidate.mode(), mode);
+    }
+
+    // Ensure a dry run reports its fee.
+    let authorization = Credits::claim_unbond_public(&private_key, 2_000, ExecutionMode::DryRun, rng).unwrap();
+    assert_eq!(authorization.base_fee().unwrap(), Microcredits::new(167_230));
+    assert_eq!(authorization.priority_fee().unwrap(), Microcredits::new(2_000));
+    assert_eq!(authorization.total_fee().unwrap(), Microcredits::new(169_230));
+
+    // Ensure a dry run is never executed.
+    let error = authorization.execute().err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "A dry run authorization is never executed, it would pay a fee of 169230 microcredits"
+    );
+}
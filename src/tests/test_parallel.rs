@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Authorized, Credits, CreditsCall, ExecutionMode, Microcredits};
+
+use rand::Rng;
+use snarkvm::synthesizer::Process;
+
+/// Samples a list of calls to every `credits.aleo` function.
+fn sample_calls(rng: &mut TestRng) -> Vec<CreditsCall> {
+    let (_, address) = sample_account(rng);
+    let amount_in_microcredits = Microcredits::new(1_000_000);
+    vec![
+        CreditsCall::BondPublic { validator: address.to_string(), amount_in_microcredits },
+        CreditsCall::UnbondPublic { amount_in_microcredits },
+        CreditsCall::UnbondDelegatorAsValidator { delegator: address.to_string() },
+        CreditsCall::ClaimUnbondPublic,
+        CreditsCall::SetValidatorState { is_open: true },
+        CreditsCall::TransferPublic { recipient: address.to_string(), amount_in_microcredits },
+        CreditsCall::TransferPublicToPrivate { recipient: address.to_string(), amount_in_microcredits },
+    ]
+}
+
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    // Ensure the process and authorizations may be shared across threads.
+    assert_send_sync::<Process<CurrentNetwork>>();
+    assert_send_sync::<Authorized<CurrentNetwork>>();
+}
+
+#[test]
+fn test_authorize_many() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and calls.
+    let (private_key, _) = sample_account(rng);
+    let calls = sample_calls(rng);
+
+    // Authorize the calls twice, from the same seed.
+    let seed = rng.gen();
+    let first = Credits::authorize_many(
+        &private_key.to_string(),
+        &calls,
+        1000,
+        ExecutionMode::Prove,
+        &mut TestRng::fixed(seed),
+    );
+    let second = Credits::authorize_many(
+        &private_key.to_string(),
+        &calls,
+        1000,
+        ExecutionMode::Prove,
+        &mut TestRng::fixed(seed),
+    );
+    assert_eq!(first.len(), calls.len());
+    assert_eq!(second.len(), calls.len());
+
+    // Ensure every call is authorized, in order, and reproducibly for the same seed.
+    for (call, (first, second)) in calls.iter().zip(first.into_iter().zip(second)) {
+        let (first, second) =
+            (serde_json::to_value(first.unwrap()).unwrap(), serde_json::to_value(second.unwrap()).unwrap());
+        assert_eq!(first["function"]["requests"][0]["function"], call.function_name());
+        assert_eq!(first, second);
+    }
+}
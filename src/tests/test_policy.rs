@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Credits, ExecutionMode, Microcredits, PolicyViolation, Spend, SpendingPolicy};
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The lock that the tests hold while they install a policy, as the installed policy is global.
+static GLOBAL_POLICY: Mutex<()> = Mutex::new(());
+
+/// A policy installed for the lifetime of the guard, which is uninstalled even if the test panics.
+struct InstalledPolicy {
+    /// The installed policy.
+    policy: Arc<SpendingPolicy>,
+    /// The lock on the global policy.
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl InstalledPolicy {
+    /// Installs the given policy, once no other test has a policy installed.
+    fn install(policy: SpendingPolicy) -> Self {
+        let lock = GLOBAL_POLICY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Self { policy: policy.install().unwrap(), _lock: lock }
+    }
+}
+
+impl Drop for InstalledPolicy {
+    /// Uninstalls the policy, before the lock is released.
+    fn drop(&mut self) {
+        let _ = SpendingPolicy::uninstall();
+    }
+}
+
+/// Returns a `transfer_public` spending of the given amount and priority fee.
+fn sample_spend(sender: &str, recipient: &str, amount: u64, priority_fee: u64) -> Spend {
+    Spend {
+        sender: sender.to_string(),
+        function_name: "transfer_public".to_string(),
+        recipient: Some(recipient.to_string()),
+        amount: Microcredits::new(amount),
+        priority_fee: Microcredits::new(priority_fee),
+    }
+}
+
+/// Returns the policy violation of the given result.
+fn violation(result: anyhow::Result<()>) -> PolicyViolation {
+    result.unwrap_err().downcast::<PolicyViolation>().unwrap()
+}
+
+#[test]
+fn test_policy_from_file() {
+    let rng = &mut TestRng::default();
+    let (_, address) = sample_account(rng);
+    let path = std::env::temp_dir().join(format!("policy_{address}.json"));
+
+    // Ensure the policy is loaded from a file.
+    let contents = format!(r#"{{ "daily_limit": 5000000, "denylist": ["{address}"], "max_priority_fee": 1000 }}"#);
+    std::fs::write(&path, contents).unwrap();
+    let policy = SpendingPolicy::from_file(&path).unwrap();
+    assert_eq!(policy.daily_limit, Some(Microcredits::new(5_000_000)));
+    assert!(policy.denylist.contains(&address.to_string()));
+    assert_eq!(policy.allowlist, None);
+
+    // Ensure an invalid address or an unknown rule fails.
+    std::fs::write(&path, r#"{ "allowlist": ["aleo1invalid"] }"#).unwrap();
+    assert!(SpendingPolicy::from_file(&path).is_err());
+    std::fs::write(&path, r#"{ "daily_limt": 5000000 }"#).unwrap();
+    assert!(SpendingPolicy::from_file(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_policy_check() {
+    let rng = &mut TestRng::default();
+    let (_, sender) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+    let (sender, recipient) = (sender.to_string(), recipient.to_string());
+
+    // Ensure the recipient lists are checked.
+    let mut policy = SpendingPolicy::default();
+    policy.denylist.insert(recipient.clone());
+    assert_eq!(violation(policy.check(&sample_spend(&sender, &recipient, 1, 0))), PolicyViolation::RecipientDenied {
+        recipient: recipient.clone()
+    });
+    let mut policy = SpendingPolicy::default();
+    policy.allowlist = Some([sender.clone()].into());
+    assert_eq!(
+        violation(policy.check(&sample_spend(&sender, &recipient, 1, 0))),
+        PolicyViolation::RecipientNotAllowed { recipient: recipient.clone() }
+    );
+
+    // Ensure the maximum priority fee is checked.
+    let mut policy = SpendingPolicy::default();
+    policy.max_priority_fee = Some(Microcredits::new(1_000));
+    policy.check(&sample_spend(&sender, &recipient, 1, 1_000)).unwrap();
+    assert_eq!(
+        violation(policy.check(&sample_spend(&sender, &recipient, 1, 1_001))),
+        PolicyViolation::PriorityFeeTooHigh {
+            priority_fee: Microcredits::new(1_001),
+            max_priority_fee: Microcredits::new(1_000)
+        }
+    );
+
+    // Ensure an amount that reaches the confirmation threshold must be approved.
+    let mut policy = SpendingPolicy::default();
+    policy.confirmation_threshold = Some(Microcredits::new(1_000_000));
+    policy.check(&sample_spend(&sender, &recipient, 999_999, 0)).unwrap();
+    assert_eq!(
+        violation(policy.check(&sample_spend(&sender, &recipient, 1_000_000, 0))),
+        PolicyViolation::ConfirmationRequired {
+            amount: Microcredits::new(1_000_000),
+            threshold: Microcredits::new(1_000_000)
+        }
+    );
+    policy.approve(&sender, &recipient, 1_000_000).unwrap();
+    policy.check(&sample_spend(&sender, &recipient, 1_000_000, 0)).unwrap();
+
+    // Ensure the daily limit of the sender takes precedence over the default limit.
+    let mut policy = SpendingPolicy::default();
+    policy.daily_limit = Some(Microcredits::new(1_000_000));
+    policy.daily_limits.insert(sender.clone(), Microcredits::new(10_000_000));
+    policy.check(&sample_spend(&sender, &recipient, 5_000_000, 0)).unwrap();
+    assert!(matches!(
+        violation(policy.check(&sample_spend(&recipient, &sender, 5_000_000, 0))),
+        PolicyViolation::DailyLimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn test_policy_refund() {
+    let rng = &mut TestRng::default();
+    let (_, sender) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+    let spend = sample_spend(&sender.to_string(), &recipient.to_string(), 1_000_000, 0);
+
+    // Ensure a refund removes the recorded spending.
+    let policy = SpendingPolicy::default();
+    let charge = policy.enforce(&spend).unwrap();
+    assert_eq!(policy.spent_today(&spend.sender).unwrap(), spend.total().unwrap());
+    policy.refund(&spend, charge).unwrap();
+    assert_eq!(policy.spent_today(&spend.sender).unwrap(), Microcredits::ZERO);
+}
+
+#[test]
+fn test_policy_install() {
+    let rng = &mut TestRng::default();
+    let (private_key, sender) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+    let (_, denied) = sample_account(rng);
+    let (private_key, sender) = (private_key.to_string(), sender.to_string());
+
+    // Install a policy that only restricts the sampled accounts, as other tests authorize concurrently.
+    let mut policy = SpendingPolicy::default();
+    policy.daily_limits.insert(sender.clone(), Microcredits::new(2_000_000));
+    policy.denylist.insert(denied.to_string());
+    let installed = InstalledPolicy::install(policy);
+    let policy = installed.policy.clone();
+
+    // Ensure a denied recipient fails before anything is signed.
+    let result = Credits::transfer_public(&private_key, &denied.to_string(), 1_000, 0, ExecutionMode::Prove, rng);
+    let error = result.err().unwrap();
+    assert!(matches!(error.downcast_ref::<PolicyViolation>(), Some(PolicyViolation::RecipientDenied { .. })));
+    assert_eq!(policy.spent_today(&sender).unwrap(), Microcredits::ZERO);
+
+    // Ensure the spending is recorded towards the daily limit, including fees.
+    let transfer = |rng: &mut TestRng| {
+        Credits::transfer_public(&private_key, &recipient.to_string(), 1_000_000, 0, ExecutionMode::Prove, rng)
+    };
+    transfer(rng).unwrap();
+    let spent = policy.spent_today(&sender).unwrap();
+    assert_eq!(spent, Microcredits::new(1_000_000 + 263_388));
+
+    // Ensure the daily limit is enforced.
+    let error = transfer(rng).err().unwrap();
+    assert_eq!(error.downcast::<PolicyViolation>().unwrap(), PolicyViolation::DailyLimitExceeded {
+        sender: sender.clone(),
+        limit: Microcredits::new(2_000_000),
+        spent,
+        requested: spent
+    });
+
+    // Ensure an uninstalled policy is no longer enforced.
+    drop(installed);
+    transfer(rng).unwrap();
+    assert_eq!(policy.spent_today(&sender).unwrap(), spent);
+}
+
+#[test]
+fn test_policy_dry_run() {
+    let rng = &mut TestRng::default();
+    let (private_key, sender) = sample_account(rng);
+    let (_, recipient) = sample_account(rng);
+    let (private_key, sender, recipient) = (private_key.to_string(), sender.to_string(), recipient.to_string());
+
+    // Install a policy whose daily limit the transfer exceeds.
+    let mut policy = SpendingPolicy::default();
+    policy.daily_limits.insert(sender.clone(), Microcredits::new(10_000_000));
+    let installed = InstalledPolicy::install(policy);
+    let transfer = |mode: ExecutionMode, rng: &mut TestRng| {
+        Credits::transfer_public(&private_key, &recipient, 20_000_000, 0, mode, rng)
+    };
+
+    // Ensure a dry run is still checked.
+    let error = transfer(ExecutionMode::DryRun, rng).err().unwrap();
+    assert!(matches!(error.downcast_ref::<PolicyViolation>(), Some(PolicyViolation::DailyLimitExceeded { .. })));
+
+    // Ensure a dry run is neither recorded nor consumes an approval, once the transfer must be approved.
+    drop(installed);
+    let mut policy = SpendingPolicy::default();
+    policy.daily_limits.insert(sender.clone(), Microcredits::new(30_000_000));
+    policy.confirmation_threshold = Some(Microcredits::new(20_000_000));
+    let installed = InstalledPolicy::install(policy);
+    installed.policy.approve(&sender, &recipient, 20_000_000).unwrap();
+    transfer(ExecutionMode::DryRun, rng).unwrap();
+    assert_eq!(installed.policy.spent_today(&sender).unwrap(), Microcredits::ZERO);
+
+    // Ensure the approval is consumed by the authorization that can be executed.
+    transfer(ExecutionMode::Prove, rng).unwrap();
+    assert_eq!(installed.policy.spent_today(&sender).unwrap(), Microcredits::new(20_000_000 + 263_388));
+    let error = transfer(ExecutionMode::DryRun, rng).err().unwrap();
+    assert!(matches!(error.downcast_ref::<PolicyViolation>(), Some(PolicyViolation::ConfirmationRequired { .. })));
+}
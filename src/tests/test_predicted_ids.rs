@@ -0,0 +1,60 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::Authorized;
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+#[test]
+fn test_predicted_ids() {
+    let rng = &mut TestRng::default();
+
+    // Sample the authorization.
+    let authorization = sample_transfer_public(rng);
+    let transaction_id = authorization.transaction_id().unwrap();
+    assert!(authorization.fee_transition_id().is_some());
+
+    // Ensure the transaction ID commits to the fee, unlike the execution ID.
+    assert_ne!(*transaction_id, authorization.execution_id().unwrap());
+
+    // Ensure the IDs are fixed once the authorization is signed.
+    let candidate = Authorized::<CurrentNetwork>::from_bytes_le(&authorization.to_bytes_le().unwrap()).unwrap();
+    assert_eq!(candidate.transaction_id().unwrap(), transaction_id);
+    assert_eq!(candidate.fee_transition_id(), authorization.fee_transition_id());
+    assert_eq!(candidate.transition_ids(), authorization.transition_ids());
+}
+
+#[test]
+fn test_predicted_ids_local() {
+    let rng = &mut TestRng::default();
+
+    // Sample the authorization.
+    let authorization = sample_transfer_public(rng);
+    // Predict the IDs of the transaction.
+    let transaction_id = authorization.transaction_id().unwrap();
+    let execution_id = authorization.execution_id().unwrap();
+    let transition_ids = authorization.transition_ids();
+    let fee_transition_id = authorization.fee_transition_id().unwrap();
+    // Execute the authorization.
+    let transaction = authorization.execute_local::<CurrentAleo, _>(rng).unwrap();
+
+    // Ensure the transaction has the predicted IDs.
+    assert_eq!(transaction.id(), transaction_id);
+    assert_eq!(transaction.execution().unwrap().to_execution_id().unwrap(), execution_id);
+    for transition_id in &transition_ids {
+        assert!(transaction.contains_transition(transition_id));
+    }
+    assert_eq!(*transaction.fee_transition().unwrap().transition_id(), fee_transition_id);
+}
@@ -0,0 +1,92 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    test_scanner::{sample_credits_transition, sample_transition},
+    *,
+};
+use crate::{Microcredits, Receipt};
+use snarkvm::{
+    console::{
+        program::{Literal, Plaintext},
+        types::{Field, U64},
+    },
+    ledger::block::{Execution, Fee, Input, Output, Transaction, Transition},
+    prelude::Uniform,
+};
+
+/// Returns a `credits.aleo` transition of the given function with the given public inputs.
+fn sample_public_transition(
+    function_name: &str,
+    inputs: Vec<Literal<CurrentNetwork>>,
+    rng: &mut TestRng,
+) -> Transition<CurrentNetwork> {
+    let inputs =
+        inputs.into_iter().map(|literal| Input::Public(Field::rand(rng), Some(Plaintext::from(literal)))).collect();
+    sample_credits_transition(function_name, inputs, vec![Output::Future(Field::rand(rng), None)], rng)
+}
+
+#[test]
+fn test_receipt() {
+    let rng = &mut TestRng::default();
+
+    // Construct a `transfer_public` transaction with a public fee.
+    let (_, recipient) = sample_account(rng);
+    let transition = sample_public_transition(
+        "transfer_public",
+        vec![Literal::Address(recipient), Literal::U64(U64::new(1_500_000))],
+        rng,
+    );
+    let fee = sample_public_transition(
+        "fee_public",
+        vec![Literal::U64(U64::new(263_388)), Literal::U64(U64::new(10_000)), Literal::Field(Field::rand(rng))],
+        rng,
+    );
+    let execution = Execution::from([transition.clone()].into_iter(), Default::default(), None).unwrap();
+    let fee = Fee::from(fee.clone(), Default::default(), None).unwrap();
+    let transaction = Transaction::from_execution(execution, Some(fee.clone())).unwrap();
+
+    // Ensure the receipt is parsed from the transaction.
+    let receipt = Receipt::try_from(&transaction).unwrap();
+    assert_eq!(receipt.transaction_id, transaction.id().to_string());
+    assert_eq!(receipt.transition_ids, vec![transition.id().to_string()]);
+    assert_eq!(receipt.program_id, "credits.aleo");
+    assert_eq!(receipt.function_name, "transfer_public");
+    assert_eq!(receipt.address(), Some(recipient));
+    assert_eq!(receipt.amount(), Some(Microcredits::new(1_500_000)));
+    assert!(receipt.record_commitments.is_empty());
+
+    // Ensure the fee is parsed from the fee transition.
+    let fee_receipt = receipt.fee.clone().unwrap();
+    assert_eq!(fee_receipt.transition_id, fee.transition_id().to_string());
+    assert_eq!(fee_receipt.function_name, "fee_public");
+    assert_eq!(fee_receipt.base_fee, Microcredits::new(263_388));
+    assert_eq!(fee_receipt.priority_fee, Microcredits::new(10_000));
+    assert_eq!(fee_receipt.total().unwrap(), Microcredits::new(273_388));
+
+    // Ensure the receipt round-trips through JSON.
+    let candidate: Receipt = serde_json::from_str(&serde_json::to_string(&receipt).unwrap()).unwrap();
+    assert_eq!(candidate, receipt);
+
+    // Ensure the commitments of the output records are parsed from a private transfer.
+    let (transition, commitment) = sample_transition(&[], &recipient, 1_500_000, rng);
+    let execution = Execution::from([transition].into_iter(), Default::default(), None).unwrap();
+    let transaction = Transaction::from_execution(execution, Some(fee.clone())).unwrap();
+    let receipt = Receipt::try_from(&transaction).unwrap();
+    assert_eq!(receipt.function_name, "transfer_private");
+    assert_eq!(receipt.record_commitments, vec![commitment.to_string()]);
+
+    // Ensure a fee transaction has no receipt.
+    assert!(Receipt::try_from(&Transaction::from_fee(fee).unwrap()).is_err());
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{CreditsCall, CreditsRequest, ExecutionMode, Microcredits};
+
+#[test]
+fn test_request_call() {
+    let rng = &mut TestRng::default();
+    let (_, address) = sample_account(rng);
+
+    // Ensure the inputs are set by name.
+    let call = CreditsRequest::transfer_public().to(address.to_string()).amount(1_000_000).call().unwrap();
+    assert_eq!(call, CreditsCall::TransferPublic {
+        recipient: address.to_string(),
+        amount_in_microcredits: Microcredits::new(1_000_000)
+    });
+    let call = CreditsRequest::set_validator_state().is_open(true).call().unwrap();
+    assert_eq!(call, CreditsCall::SetValidatorState { is_open: true });
+    assert_eq!(CreditsRequest::claim_unbond_public().call().unwrap(), CreditsCall::ClaimUnbondPublic);
+
+    // Ensure a missing input fails.
+    let error = CreditsRequest::transfer_public().amount(1_000_000).call().unwrap_err();
+    assert_eq!(error.to_string(), "'transfer_public' requires an address, set with `to`");
+    let error = CreditsRequest::bond_public().to(address.to_string()).call().unwrap_err();
+    assert_eq!(error.to_string(), "'bond_public' requires an amount");
+
+    // Ensure an input the function does not take fails.
+    let error = CreditsRequest::unbond_public().to(address.to_string()).amount(1).call().unwrap_err();
+    assert_eq!(error.to_string(), "'unbond_public' does not take an address");
+    let error = CreditsRequest::claim_unbond_public().amount(1).call().unwrap_err();
+    assert_eq!(error.to_string(), "'claim_unbond_public' does not take an amount");
+}
+
+#[test]
+fn test_request_authorize() {
+    let rng = &mut TestRng::default();
+    let (private_key, address) = sample_account(rng);
+
+    // Ensure the private key is required.
+    let request = CreditsRequest::transfer_public().to(address.to_string()).amount(1_000_000).priority_fee(0);
+    let error = request.authorize(rng).err().unwrap();
+    assert_eq!(error.to_string(), "'transfer_public' requires a private key, set with `from`");
+
+    // Ensure the explicit priority fee is used instead of the estimate.
+    let request = request.from(private_key.to_string()).priority_fee(2_000).mode(ExecutionMode::Prove);
+    assert_eq!(request.resolve_priority_fee().unwrap(), Microcredits::new(2_000));
+    let authorization = request.authorize(rng).unwrap();
+    assert_eq!(authorization.mode(), ExecutionMode::Prove);
+}
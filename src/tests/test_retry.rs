@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{RequestRejected, RetryPolicy, StatusClass, API_URL};
+
+use std::time::Duration;
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy::default().with_backoff(Duration::from_millis(100), Duration::from_millis(500));
+
+    // Ensure the backoff doubles on every retry, up to the maximum.
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+}
+
+#[test]
+fn test_retry_policy_is_retryable() {
+    // Ensure the default policy retries request timeouts, rate limits, and server errors.
+    let policy = RetryPolicy::default();
+    for status in [408, 429, 500, 502, 503, 599] {
+        assert!(policy.is_retryable(status), "{status} should be retryable");
+    }
+    for status in [200, 400, 401, 404, 422, 600] {
+        assert!(!policy.is_retryable(status), "{status} should not be retryable");
+    }
+
+    // Ensure the retryable status classes are configurable.
+    let policy = policy.with_retryable(vec![StatusClass::TooManyRequests]);
+    assert!(policy.is_retryable(429));
+    assert!(!policy.is_retryable(503));
+}
+
+#[test]
+fn test_retry_policy_endpoints() {
+    let policy = RetryPolicy::default().with_fallback("http://localhost:3030/testnet3").with_max_attempts(5);
+    assert_eq!(policy.endpoints(), [API_URL.to_string(), "http://localhost:3030/testnet3".to_string()]);
+    assert_eq!(policy.max_attempts(), 5);
+}
+
+#[test]
+fn test_retry_policy_post_retry() {
+    // Reject the first attempt with a retryable status.
+    let (url, server) = mock_server(vec![(503, "\"Service unavailable\""), (200, "\"accepted\"")]);
+    let policy = RetryPolicy::new(&url).with_backoff(Duration::ZERO, Duration::ZERO);
+
+    // Ensure the request is retried, and returns the successful response.
+    let response: String = policy.post("/execute", "{\"id\":1}").unwrap();
+    assert_eq!(response, "accepted");
+
+    // Ensure the same body was sent on every attempt.
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        assert_eq!(request.request_line, "POST /execute");
+        assert_eq!(request.body, "{\"id\":1}");
+    }
+}
+
+#[test]
+fn test_retry_policy_post_fallback() {
+    // Exhaust the attempts on the primary endpoint, before the fallback endpoint accepts the request.
+    let (primary, primary_server) = mock_server(vec![(429, "\"Too many requests\""), (500, "\"Internal error\"")]);
+    let (fallback, fallback_server) = mock_server(vec![(200, "\"accepted\"")]);
+    let policy = RetryPolicy::new(&primary)
+        .with_fallback(&fallback)
+        .with_max_attempts(2)
+        .with_backoff(Duration::ZERO, Duration::ZERO);
+
+    // Ensure the request fails over to the fallback endpoint.
+    let response: String = policy.post("/execute", "{\"id\":2}").unwrap();
+    assert_eq!(response, "accepted");
+
+    // Ensure the same body was sent to every endpoint.
+    let requests =
+        primary_server.join().unwrap().into_iter().chain(fallback_server.join().unwrap()).collect::<Vec<_>>();
+    assert_eq!(requests.len(), 3);
+    for request in requests {
+        assert_eq!(request.request_line, "POST /execute");
+        assert_eq!(request.body, "{\"id\":2}");
+    }
+
+    // Ensure a non-retryable status is returned without failing over, to an unreachable fallback endpoint.
+    let (primary, primary_server) = mock_server(vec![(400, "\"Invalid transaction\"")]);
+    let policy =
+        RetryPolicy::new(&primary).with_fallback("http://127.0.0.1:1").with_backoff(Duration::ZERO, Duration::ZERO);
+    let error = policy.post::<String>("/execute", "{\"id\":3}").unwrap_err();
+    let rejected = error.downcast_ref::<RequestRejected>().unwrap();
+    assert_eq!((rejected.endpoint.as_str(), rejected.status), (primary.as_str(), 400));
+    assert!(rejected.message.contains("Invalid transaction"), "{error}");
+    assert_eq!(primary_server.join().unwrap().len(), 1);
+}
+
+#[test]
+fn test_retry_policy_post_unreadable_response() {
+    // Close the connection before the body of the first response is fully sent, serving the responses from the end.
+    let mut responses = vec![
+        "HTTP/1.1 200 Mock\r\nContent-Type: application/json\r\nContent-Length: 10\r\nConnection: close\r\n\r\n\"accepted\"",
+        "HTTP/1.1 503 Mock\r\nContent-Length: 100\r\nConnection: close\r\n\r\nService",
+    ];
+    let (url, server) = mock_server_raw(2, move |_| responses.pop().unwrap().to_string());
+    let policy = RetryPolicy::new(&url).with_backoff(Duration::ZERO, Duration::ZERO);
+
+    // Ensure the unreadable response is retried, rather than aborting the request.
+    let response: String = policy.post("/execute", "{\"id\":4}").unwrap();
+    assert_eq!(response, "accepted");
+    assert_eq!(server.join().unwrap().len(), 2);
+}
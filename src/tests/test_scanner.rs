@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Microcredits, OwnedRecord, RecordScanner, API_URL};
+use snarkvm::{
+    console::{
+        account::{GraphKey, ViewKey},
+        network::Network,
+        program::{Identifier, Literal, Owner, Plaintext, ProgramID, Record, ToBits},
+        types::{Field, Group, Scalar},
+    },
+    ledger::block::{Execution, Input, Output, Transaction, Transition},
+    prelude::Uniform,
+};
+
+use core::str::FromStr;
+
+/// Returns a `credits.aleo` transition that spends the given records and creates a record of the given amount for the given address.
+pub(super) fn sample_transition(
+    spent: &[(Field<CurrentNetwork>, Field<CurrentNetwork>)],
+    address: &Address<CurrentNetwork>,
+    amount: u64,
+    rng: &mut TestRng,
+) -> (Transition<CurrentNetwork>, Field<CurrentNetwork>) {
+    let program_id = ProgramID::from_str("credits.aleo").unwrap();
+
+    // Encrypt a new record for the address.
+    let randomizer = Scalar::rand(rng);
+    let nonce = CurrentNetwork::g_scalar_multiply(&randomizer);
+    let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(&format!(
+        "{{ owner: {address}.private, microcredits: {amount}u64.private, _nonce: {nonce}.public }}"
+    ))
+    .unwrap();
+    let commitment = record.to_commitment(&program_id, &Identifier::from_str("credits").unwrap()).unwrap();
+    let ciphertext = record.encrypt(randomizer).unwrap();
+    let checksum = CurrentNetwork::hash_bhp1024(&ciphertext.to_bits_le()).unwrap();
+
+    // Construct the transition.
+    let inputs = spent.iter().map(|(serial_number, tag)| Input::Record(*serial_number, *tag)).collect();
+    let outputs = vec![Output::Record(commitment, checksum, Some(ciphertext))];
+    (sample_credits_transition("transfer_private", inputs, outputs, rng), commitment)
+}
+
+/// Returns a `credits.aleo` transition of the given function with the given inputs and outputs.
+pub(super) fn sample_credits_transition(
+    function_name: &str,
+    inputs: Vec<Input<CurrentNetwork>>,
+    outputs: Vec<Output<CurrentNetwork>>,
+    rng: &mut TestRng,
+) -> Transition<CurrentNetwork> {
+    Transition::new(
+        ProgramID::from_str("credits.aleo").unwrap(),
+        Identifier::from_str(function_name).unwrap(),
+        inputs,
+        outputs,
+        Group::rand(rng),
+        Field::rand(rng),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_scan_transitions() {
+    let rng = &mut TestRng::default();
+
+    let (private_key, address) = sample_account(rng);
+    let (_, other_address) = sample_account(rng);
+    let view_key = ViewKey::try_from(&private_key).unwrap();
+    let mut scanner = RecordScanner::new(API_URL, view_key).unwrap();
+
+    // Ensure only the records owned by the view key are found.
+    let (owned, commitment) = sample_transition(&[], &address, 5_000_000, rng);
+    let (other, _) = sample_transition(&[], &other_address, 7_000_000, rng);
+    assert!(scanner.scan_transitions(10, [&owned, &other]).unwrap());
+    let unspent = scanner.unspent();
+    assert_eq!(unspent.len(), 1);
+    assert_eq!(unspent[0].commitment(), commitment);
+    assert_eq!(unspent[0].height(), Some(10));
+    assert_eq!(unspent[0].microcredits().unwrap(), Microcredits::new(5_000_000));
+
+    // Ensure rescanning the same transitions is a no-op.
+    assert!(!scanner.scan_transitions(10, [&owned, &other]).unwrap());
+
+    // Ensure the tag and serial number match those published when the record is spent.
+    let sk_tag = GraphKey::try_from(&view_key).unwrap().sk_tag();
+    let tag = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::tag(sk_tag, commitment).unwrap();
+    let serial_number =
+        Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(private_key, commitment).unwrap();
+    assert_eq!(unspent[0].tag(), tag);
+    assert_eq!(unspent[0].serial_number(&private_key.to_string()).unwrap(), serial_number);
+
+    // Ensure the record is removed once it is spent, and the change is found.
+    let (change, change_commitment) = sample_transition(&[(serial_number, tag)], &address, 4_000_000, rng);
+    assert!(scanner.scan_transitions(11, [&change]).unwrap());
+    let unspent = scanner.unspent();
+    assert_eq!(unspent.len(), 1);
+    assert_eq!(unspent[0].commitment(), change_commitment);
+    assert_eq!(unspent[0].height(), Some(11));
+}
+
+#[test]
+fn test_scan_checkpoint() {
+    let rng = &mut TestRng::default();
+
+    let (private_key, address) = sample_account(rng);
+    let view_key = ViewKey::try_from(&private_key).unwrap();
+    let path = std::env::temp_dir().join(format!("scanner_{address}.json"));
+
+    // Ensure an empty range persists the checkpoint, without retrieving any block.
+    let mut scanner = RecordScanner::new(API_URL, view_key).unwrap().persist_to(&path).unwrap();
+    let (transition, _) = sample_transition(&[], &address, 1_000_000, rng);
+    scanner.scan_transitions(3, [&transition]).unwrap();
+    assert_eq!(scanner.scan(3..3).unwrap().len(), 1);
+
+    // Ensure the records are restored from the checkpoint.
+    let scanner = RecordScanner::new(API_URL, view_key).unwrap().persist_to(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let unspent: Vec<OwnedRecord> = scanner.unspent();
+    assert_eq!(unspent.len(), 1);
+    assert_eq!(scanner.height(), None);
+}
+
+#[test]
+fn test_from_transaction() {
+    let rng = &mut TestRng::default();
+
+    let (private_key, address) = sample_account(rng);
+    let (other_private_key, _) = sample_account(rng);
+    let view_key = ViewKey::try_from(&private_key).unwrap();
+
+    // Construct a transaction that creates a record for the address.
+    let (transition, commitment) = sample_transition(&[], &address, 2_500_000, rng);
+    let execution = Execution::from([transition].into_iter(), Default::default(), None).unwrap();
+    let transaction = Transaction::from_execution(execution, None).unwrap();
+
+    // Ensure the recipient decrypts the record, without scanning a block.
+    let record = OwnedRecord::from_transaction(&transaction, &view_key).unwrap();
+    assert_eq!(record.commitment(), commitment);
+    assert_eq!(record.microcredits().unwrap(), Microcredits::new(2_500_000));
+    assert_eq!(record.height(), None);
+    assert_eq!(*record.record().owner(), Owner::Private(Plaintext::from(Literal::Address(address))));
+
+    // Ensure another view key finds no record.
+    assert!(OwnedRecord::from_transaction(&transaction, &ViewKey::try_from(&other_private_key).unwrap()).is_err());
+}
@@ -0,0 +1,46 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{Credits, ExecutionMode, SecretString};
+
+#[test]
+fn test_secret_string() {
+    let rng = &mut TestRng::default();
+
+    // Sample a private key.
+    let (private_key, address) = sample_account(rng);
+    let secret = SecretString::from(private_key.to_string());
+    assert_eq!(secret.expose(), private_key.to_string());
+
+    // Ensure the secret is redacted when debugged.
+    assert_eq!(format!("{secret:?}"), "SecretString(***)");
+    assert!(!format!("{:?}", Some(&secret)).contains(&private_key.to_string()));
+
+    // Ensure the secret is read from a file, without surrounding whitespace.
+    let path = std::env::temp_dir().join(format!("secret_{address}.key"));
+    std::fs::write(&path, format!("  {private_key}\n")).unwrap();
+    let candidate = SecretString::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(candidate, secret);
+
+    // Ensure the secret is read from an environment variable.
+    let name = format!("CREDITS_TEST_SECRET_{address}");
+    assert!(SecretString::from_env(&name).is_err());
+    std::env::set_var(&name, private_key.to_string());
+    assert_eq!(SecretString::from_env(&name).unwrap(), secret);
+
+    // Ensure the secret is accepted as the private key of a `Credits` function.
+    assert!(Credits::claim_unbond_public(&secret, 0, ExecutionMode::Prove, rng).is_ok());
+}
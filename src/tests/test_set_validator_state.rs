@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::{Authorized, Credits};
+use crate::{Authorized, Credits, ExecutionMode};
 
 use rand::Rng;
 
@@ -27,8 +27,14 @@ pub(crate) fn sample_set_validator_state(rng: &mut TestRng) -> Authorized<Curren
     let priority_fee_in_microcredits = rng.gen_range(1..1000000);
 
     // Sample the authorization.
-    Credits::set_validator_state(&validator_private_key.to_string(), is_open, priority_fee_in_microcredits, false, rng)
-        .unwrap()
+    Credits::set_validator_state(
+        &validator_private_key.to_string(),
+        is_open,
+        priority_fee_in_microcredits,
+        ExecutionMode::Prove,
+        rng,
+    )
+    .unwrap()
 }
 
 #[test]
@@ -0,0 +1,272 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    BondState,
+    Microcredits,
+    RequestRejected,
+    StakeStatus,
+    StakeStep,
+    StakeWorkflow,
+    MIN_DELEGATOR_STAKE_IN_MICROCREDITS,
+    MIN_VALIDATOR_STAKE_IN_MICROCREDITS,
+};
+
+/// Samples a workflow that moves the minimum delegator stake,
+/// returning the staker's private key, the current and new validators, and the workflow.
+fn sample_move_stake(rng: &mut TestRng) -> (String, Address<CurrentNetwork>, Address<CurrentNetwork>, StakeWorkflow) {
+    let (private_key, staker) = sample_account(rng);
+    let (_, validator) = sample_account(rng);
+    let (_, new_validator) = sample_account(rng);
+    let bond_state = BondState { validator, microcredits: Microcredits::new(MIN_DELEGATOR_STAKE_IN_MICROCREDITS) };
+    let workflow =
+        StakeWorkflow::plan_move_stake(&staker.to_string(), &bond_state, &new_validator.to_string()).unwrap();
+    (private_key.to_string(), validator, new_validator, workflow)
+}
+
+#[test]
+fn test_plan_unbond_all() {
+    let rng = &mut TestRng::default();
+
+    // Sample the staker and validator.
+    let (_, staker) = sample_account(rng);
+    let (_, validator) = sample_account(rng);
+    let bond_state = BondState { validator, microcredits: Microcredits::new(5_000_000) };
+
+    // Plan the workflow.
+    let workflow = StakeWorkflow::plan_unbond_all(&staker.to_string(), &bond_state).unwrap();
+    assert_eq!(workflow.steps(), &[StakeStep::Unbond { microcredits: bond_state.microcredits }, StakeStep::Claim]);
+    assert_eq!(workflow.next_step(), Some(&workflow.steps()[0]));
+    assert!(!workflow.is_completed());
+
+    // Ensure there must be stake to unbond.
+    let bond_state = BondState { validator, microcredits: Microcredits::ZERO };
+    assert!(StakeWorkflow::plan_unbond_all(&staker.to_string(), &bond_state).is_err());
+}
+
+#[test]
+fn test_plan_move_stake() {
+    let rng = &mut TestRng::default();
+
+    // Sample the staker and validators.
+    let (_, staker) = sample_account(rng);
+    let (_, validator) = sample_account(rng);
+    let (_, new_validator) = sample_account(rng);
+    let microcredits = Microcredits::new(MIN_DELEGATOR_STAKE_IN_MICROCREDITS);
+    let bond_state = BondState { validator, microcredits };
+
+    // Plan the workflow.
+    let workflow =
+        StakeWorkflow::plan_move_stake(&staker.to_string(), &bond_state, &new_validator.to_string()).unwrap();
+    assert_eq!(workflow.steps(), &[StakeStep::Unbond { microcredits }, StakeStep::Claim, StakeStep::Bond {
+        validator: new_validator,
+        microcredits
+    }]);
+
+    // Ensure a stake below the minimum delegator stake cannot be moved.
+    let bond_state = BondState { validator, microcredits: Microcredits::new(MIN_DELEGATOR_STAKE_IN_MICROCREDITS - 1) };
+    assert!(StakeWorkflow::plan_move_stake(&staker.to_string(), &bond_state, &new_validator.to_string()).is_err());
+
+    // Ensure a validator cannot move their own stake.
+    let bond_state = BondState { validator, microcredits };
+    assert!(StakeWorkflow::plan_move_stake(&validator.to_string(), &bond_state, &new_validator.to_string()).is_err());
+}
+
+#[test]
+fn test_plan_rebond_validator() {
+    let rng = &mut TestRng::default();
+    let (_, validator) = sample_account(rng);
+
+    // Ensure a validator may rebond a stake that meets the minimum validator stake.
+    let microcredits = Microcredits::new(MIN_VALIDATOR_STAKE_IN_MICROCREDITS);
+    let bond_state = BondState { validator, microcredits };
+    let workflow = StakeWorkflow::plan_move_stake(&validator.to_string(), &bond_state, &validator.to_string()).unwrap();
+    assert_eq!(workflow.steps()[2], StakeStep::Bond { validator, microcredits });
+
+    // Ensure a validator cannot rebond a stake below the minimum validator stake, even if a delegator could.
+    let bond_state = BondState { validator, microcredits: Microcredits::new(MIN_VALIDATOR_STAKE_IN_MICROCREDITS - 1) };
+    let error =
+        StakeWorkflow::plan_move_stake(&validator.to_string(), &bond_state, &validator.to_string()).unwrap_err();
+    assert!(error.to_string().contains("minimum validator stake"));
+}
+
+#[test]
+fn test_stake_workflow_save_and_load() {
+    let rng = &mut TestRng::default();
+
+    // Sample the workflow.
+    let (_, staker) = sample_account(rng);
+    let (_, validator) = sample_account(rng);
+    let (_, new_validator) = sample_account(rng);
+    let bond_state = BondState { validator, microcredits: Microcredits::new(MIN_DELEGATOR_STAKE_IN_MICROCREDITS) };
+    let mut workflow =
+        StakeWorkflow::plan_move_stake(&staker.to_string(), &bond_state, &new_validator.to_string()).unwrap();
+
+    // Save and load the workflow.
+    let path = std::env::temp_dir().join(format!("stake_workflow_{staker}.json"));
+    workflow.save(&path).unwrap();
+    let candidate = StakeWorkflow::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(workflow, candidate);
+}
+
+#[test]
+fn test_stake_workflow_advance() {
+    let rng = &mut TestRng::default();
+    let (private_key, validator, _, mut workflow) = sample_move_stake(rng);
+    let microcredits = MIN_DELEGATOR_STAKE_IN_MICROCREDITS;
+
+    // Unbond the stake.
+    let (url, server) = mock_node(vec![bonded(&validator, microcredits), none(), (200, TRANSACTION.to_string())]);
+    let status = workflow.advance(&url, &private_key, 0u64, rng).unwrap();
+    let unbond = authorization_of(&server.join().unwrap()[2]);
+    assert_eq!(status, StakeStatus::Executed(unbond.transaction_id().unwrap().to_string()));
+    assert_eq!(workflow.next_step(), Some(&StakeStep::Claim));
+    assert_eq!(workflow.pending_transaction_id(), None);
+
+    // Ensure the claim waits for the unbonding height.
+    let (url, server) = mock_node(vec![none(), unbonding(microcredits, 100), (200, "99".to_string())]);
+    assert_eq!(workflow.advance(&url, &private_key, 0u64, rng).unwrap(), StakeStatus::Waiting);
+    assert_eq!(server.join().unwrap()[2].request_line, "GET /latest/height");
+
+    // Claim the unbonded stake.
+    let (url, server) =
+        mock_node(vec![none(), unbonding(microcredits, 100), (200, "100".to_string()), (200, TRANSACTION.to_string())]);
+    let status = workflow.advance(&url, &private_key, 0u64, rng).unwrap();
+    let claim = authorization_of(&server.join().unwrap()[3]);
+    assert_eq!(status, StakeStatus::Executed(claim.transaction_id().unwrap().to_string()));
+
+    // Ensure the bond waits for the claim to be finalized.
+    let (url, server) = mock_node(vec![none(), unbonding(microcredits, 100)]);
+    assert_eq!(workflow.advance(&url, &private_key, 0u64, rng).unwrap(), StakeStatus::Waiting);
+    assert_eq!(server.join().unwrap().len(), 2);
+
+    // Bond the stake to the new validator.
+    let (url, server) = mock_node(vec![none(), none(), (200, TRANSACTION.to_string())]);
+    let status = workflow.advance(&url, &private_key, 0u64, rng).unwrap();
+    let bond = authorization_of(&server.join().unwrap()[2]);
+    assert_eq!(status, StakeStatus::Executed(bond.transaction_id().unwrap().to_string()));
+
+    // Ensure the workflow is completed, without any further request.
+    assert!(workflow.is_completed());
+    assert_eq!(workflow.transaction_ids().len(), 3);
+    assert_eq!(workflow.advance("http://127.0.0.1:1", &private_key, 0u64, rng).unwrap(), StakeStatus::Completed);
+
+    // Ensure a workflow may only be advanced by the staker.
+    let (other_private_key, _) = sample_account(rng);
+    assert!(workflow.advance("http://127.0.0.1:1", &other_private_key.to_string(), 0u64, rng).is_err());
+}
+
+#[test]
+fn test_stake_workflow_advance_waiting() {
+    let rng = &mut TestRng::default();
+    let (private_key, validator, new_validator, workflow) = sample_move_stake(rng);
+    let microcredits = MIN_DELEGATOR_STAKE_IN_MICROCREDITS;
+
+    // Returns the workflow with the given number of steps executed.
+    let executed = |num_steps: usize| {
+        let mut value = serde_json::to_value(&workflow).unwrap();
+        value["transaction_ids"] = serde_json::json!(vec!["at1placeholder"; num_steps]);
+        serde_json::from_value::<StakeWorkflow>(value).unwrap()
+    };
+
+    for (num_steps, responses) in [
+        // The claim waits while the unbond is not yet finalized.
+        (1, vec![bonded(&validator, microcredits), none()]),
+        (1, vec![none(), none()]),
+        // The claim waits while the stake is bonded again.
+        (1, vec![bonded(&validator, microcredits), unbonding(microcredits, 100)]),
+        // The claim waits for the unbonding height.
+        (1, vec![none(), unbonding(microcredits, 100), (200, "99".to_string())]),
+        // The bond waits while the claim is not yet finalized.
+        (2, vec![none(), unbonding(microcredits, 100)]),
+        // The bond waits while the stake is already bonded.
+        (2, vec![bonded(&new_validator, microcredits), none()]),
+    ] {
+        let mut workflow = executed(num_steps);
+        let num_requests = responses.len();
+        let (url, server) = mock_node(responses);
+        assert_eq!(workflow.advance(&url, &private_key, 0u64, rng).unwrap(), StakeStatus::Waiting);
+        // Ensure nothing was submitted, and the workflow did not advance.
+        assert_eq!(server.join().unwrap().len(), num_requests);
+        assert_eq!(workflow.transaction_ids().len(), num_steps);
+        assert_eq!(workflow.pending_transaction_id(), None);
+    }
+}
+
+#[test]
+fn test_stake_workflow_resume() {
+    let rng = &mut TestRng::default();
+    let (private_key, validator, _, mut workflow) = sample_move_stake(rng);
+    let microcredits = MIN_DELEGATOR_STAKE_IN_MICROCREDITS;
+    let path = std::env::temp_dir().join(format!("stake_workflow_resume_{}.json", workflow.staker()));
+    workflow.save(&path).unwrap();
+
+    // Submit the unbond, without a response from the endpoint.
+    let unavailable = (503, "\"Service unavailable\"".to_string());
+    let (url, server) = mock_node(vec![
+        bonded(&validator, microcredits),
+        none(),
+        unavailable.clone(),
+        unavailable.clone(),
+        unavailable.clone(),
+    ]);
+    assert!(workflow.advance(&url, &private_key, 0u64, rng).is_err());
+    let unbond = authorization_of(&server.join().unwrap()[2]);
+
+    // Ensure the unbond is persisted as pending, with its predicted transaction ID.
+    let transaction_id = unbond.transaction_id().unwrap().to_string();
+    let mut workflow = StakeWorkflow::load(&path).unwrap();
+    assert_eq!(workflow.pending_transaction_id(), Some(transaction_id.as_str()));
+    assert_eq!(workflow.next_step(), Some(&StakeStep::Unbond { microcredits: Microcredits::new(microcredits) }));
+
+    // Ensure the resumed unbond is found on chain, and recorded without being submitted again.
+    let (url, server) = mock_node(vec![(200, format!("\"{transaction_id}\""))]);
+    assert_eq!(workflow.advance(&url, &private_key, 0u64, rng).unwrap(), StakeStatus::Executed(transaction_id));
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].request_line, format!("GET /find/transactionID/{}", unbond.transition_ids()[0]));
+
+    // Submit the claim, without a response from the endpoint.
+    let (url, server) = mock_node(vec![
+        none(),
+        unbonding(microcredits, 100),
+        (200, "100".to_string()),
+        unavailable.clone(),
+        unavailable.clone(),
+        unavailable,
+    ]);
+    assert!(workflow.advance(&url, &private_key, 0u64, rng).is_err());
+    let claim = server.join().unwrap().remove(3);
+
+    // Ensure the resumed claim that is not found on chain is resubmitted with the same authorization.
+    let mut workflow = StakeWorkflow::load(&path).unwrap();
+    let (url, server) = mock_node(vec![(404, String::new()), (200, TRANSACTION.to_string())]);
+    let status = workflow.advance(&url, &private_key, 0u64, rng).unwrap();
+    let requests = server.join().unwrap();
+    assert_eq!(requests[1].body, claim.body);
+    assert_eq!(status, StakeStatus::Executed(authorization_of(&claim).transaction_id().unwrap().to_string()));
+
+    // Ensure a rejected bond is discarded, so that it is authorized again.
+    let (url, server) = mock_node(vec![none(), none(), (400, "\"Invalid transaction\"".to_string())]);
+    let error = workflow.advance(&url, &private_key, 0u64, rng).unwrap_err();
+    assert!(error.is::<RequestRejected>());
+    assert_eq!(server.join().unwrap().len(), 3);
+    let workflow = StakeWorkflow::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(workflow.pending_transaction_id(), None);
+    assert_eq!(workflow.transaction_ids().len(), 2);
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{test_scanner::sample_transition, *};
+use crate::{
+    get_base_fee_in_microcredits,
+    Microcredits,
+    OwnedRecord,
+    RecordScanner,
+    RecordStatus,
+    RecordStore,
+    Selection,
+    API_URL,
+};
+use snarkvm::console::account::ViewKey;
+
+/// Samples unspent records of the given amounts, owned by a new account.
+fn sample_records(amounts: &[u64], rng: &mut TestRng) -> (Address<CurrentNetwork>, Vec<OwnedRecord>) {
+    let (private_key, address) = sample_account(rng);
+    let mut scanner = RecordScanner::new(API_URL, ViewKey::try_from(&private_key).unwrap()).unwrap();
+    for (height, amount) in amounts.iter().enumerate() {
+        let (transition, _) = sample_transition(&[], &address, *amount, rng);
+        scanner.scan_transitions(height as u32, [&transition]).unwrap();
+    }
+    (address, scanner.unspent())
+}
+
+#[test]
+fn test_select() {
+    let rng = &mut TestRng::default();
+
+    let (address, records) = sample_records(&[3_000_000, 1_000_000, 5_000_000, 2_000_000], rng);
+    let path = std::env::temp_dir().join(format!("store_{address}.json"));
+    let store = RecordStore::open(&path).unwrap();
+    store.sync(&records).unwrap();
+    assert_eq!(store.balance().unwrap(), Microcredits::new(11_000_000));
+
+    // Ensure the smallest record that covers the amount and fee is selected.
+    let reservation = store.select(2_500_000, 100_000).unwrap();
+    assert_eq!(reservation.selection, Selection::Single(Box::new(records[0].clone())));
+    assert_eq!(reservation.id, records[0].commitment().to_string());
+    assert_eq!(store.balance().unwrap(), Microcredits::new(8_000_000));
+
+    // Ensure the largest records are joined when no single record covers the amount.
+    let join = store.select(6_000_000, 100_000).unwrap();
+    assert_eq!(join.selection, Selection::Join(vec![records[2].clone(), records[3].clone()]));
+    assert_eq!(join.selection.microcredits().unwrap(), Microcredits::new(7_000_000));
+    assert!(store.select(1_000_001, 0).is_err());
+
+    // Ensure a released reservation may be selected again, and a confirmed one may not.
+    store.release(&join.id).unwrap();
+    store.confirm(&reservation.id).unwrap();
+    assert!(store.release(&reservation.id).is_err());
+    assert_eq!(store.get(&records[0].commitment()).unwrap().unwrap().status, RecordStatus::Spent);
+    assert_eq!(store.balance().unwrap(), Microcredits::new(8_000_000));
+
+    // Ensure the statuses are restored from the file.
+    let store = RecordStore::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(store.balance().unwrap(), Microcredits::new(8_000_000));
+
+    // Ensure records that are no longer unspent are marked as spent.
+    store.sync(&records[2..]).unwrap();
+    assert_eq!(store.balance().unwrap(), Microcredits::new(7_000_000));
+    assert_eq!(store.get(&records[1].commitment()).unwrap().unwrap().status, RecordStatus::Spent);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_select_join_fees() {
+    let rng = &mut TestRng::default();
+
+    let (address, records) = sample_records(&[4_000_000, 2_000_000, 1_000_000], rng);
+    let path = std::env::temp_dir().join(format!("store_{address}.json"));
+    let store = RecordStore::open(&path).unwrap();
+    store.sync(&records).unwrap();
+    let join_fee = get_base_fee_in_microcredits("credits.aleo", "join").unwrap();
+
+    // Ensure the base fee of a join is covered, even when the joined records already cover the amount and fee.
+    let reservation = store.select(5_900_000, 100_000).unwrap();
+    assert_eq!(reservation.selection, Selection::Join(records.clone()));
+    store.release(&reservation.id).unwrap();
+
+    // Ensure the base fees of both joins are covered.
+    let reservation = store.select(7_000_000 - 2 * join_fee, 0).unwrap();
+    assert_eq!(reservation.selection.microcredits().unwrap(), Microcredits::new(7_000_000));
+    store.release(&reservation.id).unwrap();
+    assert!(store.select(7_000_000 - 2 * join_fee + 1, 0).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_select_concurrently() {
+    let rng = &mut TestRng::default();
+
+    let (address, records) = sample_records(&[1_000_000; 8], rng);
+    let path = std::env::temp_dir().join(format!("store_{address}.json"));
+    let store = RecordStore::open(&path).unwrap();
+    store.sync(&records).unwrap();
+
+    // Ensure concurrent selections never reserve the same record.
+    let reservations = std::thread::scope(|scope| {
+        let handles = (0..8).map(|_| scope.spawn(|| store.select(500_000, 0).unwrap())).collect::<Vec<_>>();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+    });
+    let mut ids = reservations.iter().map(|reservation| reservation.id.clone()).collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 8);
+    assert!(store.select(500_000, 0).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,86 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{get_base_fee_in_microcredits, Credits, ExecutionMode, Microcredits, API_URL};
+
+#[test]
+fn test_sweep_amount() {
+    // Retrieve the base fee for `transfer_public`.
+    let base_fee = get_base_fee_in_microcredits("credits.aleo", "transfer_public").unwrap();
+    let priority_fee = Microcredits::new(1000);
+
+    // Ensure the remainder is the balance net of the base fee and priority fee.
+    let balance = Microcredits::new(base_fee + 1000 + 1);
+    assert_eq!(Credits::sweep_amount(balance, priority_fee).unwrap(), Microcredits::new(1));
+    let balance = Microcredits::new(10_000_000);
+    assert_eq!(Credits::sweep_amount(balance, Microcredits::ZERO).unwrap(), Microcredits::new(10_000_000 - base_fee));
+
+    // Ensure a zero balance is rejected.
+    assert!(Credits::sweep_amount(Microcredits::ZERO, priority_fee).is_err());
+    // Ensure a balance that only covers the fees is rejected.
+    assert!(Credits::sweep_amount(Microcredits::new(base_fee + 1000), priority_fee).is_err());
+    // Ensure a balance that does not cover the fees is rejected.
+    assert!(Credits::sweep_amount(Microcredits::new(base_fee), priority_fee).is_err());
+}
+
+#[test]
+fn test_sweep_public() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender and recipient.
+    let (sender_private_key, sender_address) = sample_account(rng);
+    let (_, recipient_address) = sample_account(rng);
+
+    // Ensure the balance is read from the given endpoint.
+    let (url, server) = mock_server(vec![(200, "\"10000000u64\""), (200, "null")]);
+    let sweep = |rng: &mut TestRng| {
+        Credits::sweep_public(
+            &url,
+            &sender_private_key.to_string(),
+            &recipient_address.to_string(),
+            1000,
+            ExecutionMode::Prove,
+            rng,
+        )
+    };
+    let authorization = sweep(rng).unwrap();
+    assert_eq!(authorization.priority_fee().unwrap(), Microcredits::new(1000));
+
+    // Ensure a missing balance has nothing to sweep.
+    assert!(sweep(rng).is_err());
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].request_line, format!("GET /program/credits.aleo/mapping/account/{sender_address}"));
+}
+
+#[test]
+fn test_sweep_public_remote() {
+    let rng = &mut TestRng::default();
+
+    // Sample the sender.
+    let (sender_private_key, _) = sample_account(rng);
+    // Sample the recipient.
+    let (_, recipient_address) = sample_account(rng);
+
+    // Ensure a new account has nothing to sweep.
+    let result = Credits::sweep_public(
+        API_URL,
+        &sender_private_key.to_string(),
+        &recipient_address.to_string(),
+        1000,
+        ExecutionMode::Prove,
+        rng,
+    );
+    assert!(result.is_err());
+}
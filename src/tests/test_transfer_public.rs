@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::{Authorized, Credits};
+use crate::{Authorized, Credits, ExecutionMode};
 
 use rand::Rng;
 
@@ -34,7 +34,7 @@ pub(crate) fn sample_transfer_public(rng: &mut TestRng) -> Authorized<CurrentNet
         &recipient_address.to_string(),
         amount_in_microcredits,
         priority_fee_in_microcredits,
-        false,
+        ExecutionMode::Prove,
         rng,
     )
     .unwrap()
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::{Authorized, Credits};
+use crate::{Authorized, Credits, ExecutionMode};
 
 use rand::Rng;
 
@@ -34,7 +34,7 @@ pub(crate) fn sample_transfer_public_to_private(rng: &mut TestRng) -> Authorized
         &recipient_address.to_string(),
         amount_in_microcredits,
         priority_fee_in_microcredits,
-        false,
+        ExecutionMode::Prove,
         rng,
     )
     .unwrap()
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::{Authorized, Credits};
+use crate::{Authorized, Credits, ExecutionMode};
 
 use rand::Rng;
 
@@ -31,7 +31,7 @@ pub(crate) fn sample_unbond_delegator_as_validator(rng: &mut TestRng) -> Authori
         &validator_private_key.to_string(),
         &delegator_address.to_string(),
         priority_fee_in_microcredits,
-        false,
+        ExecutionMode::Prove,
         rng,
     )
     .unwrap()
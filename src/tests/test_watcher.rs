@@ -0,0 +1,204 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{ClaimStatus, ClaimWatcher, API_URL};
+
+#[test]
+fn test_claim_watcher_watch() {
+    let rng = &mut TestRng::default();
+
+    // Sample the stakers.
+    let (private_key_0, address_0) = sample_account(rng);
+    let (private_key_1, address_1) = sample_account(rng);
+
+    // Watch the stakers, including a duplicate.
+    let mut watcher = ClaimWatcher::new(API_URL, 1000);
+    assert_eq!(watcher.watch(&private_key_0.to_string()).unwrap(), address_0);
+    assert_eq!(watcher.watch(&private_key_1.to_string()).unwrap(), address_1);
+    assert_eq!(watcher.watch(&private_key_0.to_string()).unwrap(), address_0);
+
+    // Ensure each staker is only watched once.
+    assert_eq!(watcher.stakers().unwrap(), vec![address_0, address_1]);
+    // Ensure an invalid private key is rejected.
+    assert!(watcher.watch(&address_0.to_string()).is_err());
+}
+
+#[test]
+fn test_claim_watcher_persist_to() {
+    let rng = &mut TestRng::default();
+
+    // Sample the staker.
+    let (_, address) = sample_account(rng);
+
+    // Persist a previously claimed unbonding height.
+    let path = std::env::temp_dir().join(format!("claim_watcher_{address}.json"));
+    std::fs::write(&path, format!("{{ \"claimed\": {{ \"{address}\": 1234 }} }}")).unwrap();
+
+    // Ensure the watcher loads the claimed unbonding heights.
+    let watcher = ClaimWatcher::new(API_URL, 1000).persist_to(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(watcher.claimed_height(&address), Some(1234));
+}
+
+#[test]
+fn test_claim_watcher_remote() {
+    let rng = &mut TestRng::default();
+
+    // Sample the staker.
+    let (private_key, address) = sample_account(rng);
+
+    // Poll the watcher.
+    let mut watcher = ClaimWatcher::new(API_URL, 1000);
+    watcher.watch(&private_key.to_string()).unwrap();
+    let statuses = watcher.poll(rng).unwrap();
+
+    // Ensure a new account has nothing to claim.
+    assert_eq!(statuses, vec![(address, crate::ClaimStatus::NotUnbonding)]);
+}
+
+#[test]
+fn test_claim_watcher_claim() {
+    let rng = &mut TestRng::default();
+
+    // Watch the staker.
+    let (private_key, address) = sample_account(rng);
+    let (url, server) = mock_node(vec![
+        (200, "99".to_string()),
+        unbonding(1_000_000, 100),
+        (200, "100".to_string()),
+        unbonding(1_000_000, 100),
+        (200, TRANSACTION.to_string()),
+        (200, "101".to_string()),
+        unbonding(1_000_000, 100),
+        (200, "102".to_string()),
+        none(),
+    ]);
+    let mut watcher = ClaimWatcher::new(&url, 1000);
+    watcher.watch(&private_key.to_string()).unwrap();
+
+    // Ensure the claim waits for the unbonding height.
+    assert_eq!(watcher.poll(rng).unwrap(), vec![(address, ClaimStatus::Pending(100))]);
+    assert_eq!(watcher.claimed_height(&address), None);
+
+    // Ensure the unbonding amount is claimed once the unbonding height is reached.
+    let statuses = watcher.poll(rng).unwrap();
+    assert_eq!(watcher.claimed_height(&address), Some(100));
+
+    // Ensure the same unbonding amount is not claimed again, while the claim is waiting to be finalized.
+    assert_eq!(watcher.poll(rng).unwrap(), vec![(address, ClaimStatus::AlreadyClaimed)]);
+    assert_eq!(watcher.poll(rng).unwrap(), vec![(address, ClaimStatus::NotUnbonding)]);
+
+    // Ensure the claim was submitted once.
+    let requests = server.join().unwrap();
+    let claim = authorization_of(&requests[4]);
+    assert_eq!(statuses, vec![(address, ClaimStatus::Claimed(claim.transaction_id().unwrap().to_string()))]);
+    assert_eq!(requests.iter().filter(|request| request.request_line == "POST /execute").count(), 1);
+}
+
+#[test]
+fn test_claim_watcher_rejected() {
+    let rng = &mut TestRng::default();
+
+    // Watch the staker.
+    let (private_key, address) = sample_account(rng);
+    let (url, server) = mock_node(vec![
+        (200, "100".to_string()),
+        unbonding(1_000_000, 100),
+        (400, "\"Invalid transaction\"".to_string()),
+        (200, "101".to_string()),
+        unbonding(1_000_000, 100),
+        (200, TRANSACTION.to_string()),
+    ]);
+    let mut watcher = ClaimWatcher::new(&url, 1000);
+    watcher.watch(&private_key.to_string()).unwrap();
+
+    // Ensure a rejected claim is reverted.
+    let statuses = watcher.poll(rng).unwrap();
+    assert!(matches!(&statuses[0].1, ClaimStatus::Failed(error) if error.contains("400")));
+    assert_eq!(watcher.claimed_height(&address), None);
+
+    // Ensure the claim is retried with a new authorization on the next poll.
+    let statuses = watcher.poll(rng).unwrap();
+    let requests = server.join().unwrap();
+    let claim = authorization_of(&requests[5]);
+    assert_eq!(statuses, vec![(address, ClaimStatus::Claimed(claim.transaction_id().unwrap().to_string()))]);
+    assert_ne!(requests[2].body, requests[5].body);
+    assert_eq!(watcher.claimed_height(&address), Some(100));
+}
+
+#[test]
+fn test_claim_watcher_unconfirmed() {
+    let rng = &mut TestRng::default();
+
+    // Watch the staker, persisting the claims.
+    let (private_key, address) = sample_account(rng);
+    let path = std::env::temp_dir().join(format!("claim_watcher_unconfirmed_{address}.json"));
+    let open = |url: &str| {
+        let mut watcher = ClaimWatcher::new(url, 1000).persist_to(&path).unwrap();
+        watcher.watch(&private_key.to_string()).unwrap();
+        watcher
+    };
+    let unavailable = (503, "\"Service unavailable\"".to_string());
+
+    // Submit a claim, without a response from the endpoint.
+    let (url, server) = mock_node(vec![
+        (200, "100".to_string()),
+        unbonding(1_000_000, 100),
+        unavailable.clone(),
+        unavailable.clone(),
+        unavailable.clone(),
+    ]);
+    let statuses = open(&url).poll(rng).unwrap();
+    assert!(matches!(&statuses[0].1, ClaimStatus::Failed(error) if error.contains("every endpoint")));
+    let claim = authorization_of(&server.join().unwrap()[2]);
+    let transaction_id = claim.transaction_id().unwrap().to_string();
+
+    // Ensure the claim is kept after a restart, and confirmed on chain without being submitted again.
+    let (url, server) =
+        mock_node(vec![(200, "101".to_string()), unbonding(1_000_000, 100), (200, format!("\"{transaction_id}\""))]);
+    let mut watcher = open(&url);
+    assert_eq!(watcher.claimed_height(&address), Some(100));
+    assert_eq!(watcher.poll(rng).unwrap(), vec![(address, ClaimStatus::Claimed(transaction_id))]);
+    let requests = server.join().unwrap();
+    assert_eq!(requests[2].request_line, format!("GET /find/transactionID/{}", claim.transition_ids()[0]));
+
+    // Ensure a confirmed claim is not looked up again.
+    let (url, server) = mock_node(vec![(200, "102".to_string()), unbonding(1_000_000, 100)]);
+    assert_eq!(open(&url).poll(rng).unwrap(), vec![(address, ClaimStatus::AlreadyClaimed)]);
+    assert_eq!(server.join().unwrap().len(), 2);
+
+    // Submit the claim of a new unbonding amount, without a response from the endpoint.
+    let (url, server) = mock_node(vec![
+        (200, "200".to_string()),
+        unbonding(2_000_000, 200),
+        unavailable.clone(),
+        unavailable.clone(),
+        unavailable,
+    ]);
+    assert!(matches!(open(&url).poll(rng).unwrap()[0].1, ClaimStatus::Failed(_)));
+    let claim = server.join().unwrap().remove(2);
+
+    // Ensure a claim that is not found on chain is resubmitted with the same authorization.
+    let (url, server) = mock_node(vec![
+        (200, "201".to_string()),
+        unbonding(2_000_000, 200),
+        (404, String::new()),
+        (200, TRANSACTION.to_string()),
+    ]);
+    let transaction_id = authorization_of(&claim).transaction_id().unwrap().to_string();
+    assert_eq!(open(&url).poll(rng).unwrap(), vec![(address, ClaimStatus::Claimed(transaction_id))]);
+    assert_eq!(server.join().unwrap()[3].body, claim.body);
+    std::fs::remove_file(&path).unwrap();
+}
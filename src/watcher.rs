@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2023 Howard Wu
+// This file is part of the credits library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    file::write_atomically,
+    get_latest_height,
+    get_unbond_state,
+    secret::parse_private_key,
+    Authorized,
+    Credits,
+    ExecutionMode,
+    Microcredits,
+    RequestRejected,
+    RetryPolicy,
+    N,
+};
+use snarkvm::{
+    console::account::{Address, PrivateKey},
+    prelude::{Deserialize, Serialize},
+};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+/// The status of a watched staker after a poll.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    /// The staker has nothing unbonding.
+    NotUnbonding,
+    /// The unbonding amount may be claimed at the given block height.
+    Pending(u32),
+    /// The unbonding amount was claimed with the given transaction ID.
+    Claimed(String),
+    /// The unbonding amount was already claimed, and the claim is waiting to be finalized.
+    AlreadyClaimed,
+    /// The claim failed with the given error.
+    Failed(String),
+}
+
+/// The claims of a watcher, as persisted.
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct Claims {
+    /// The unbonding heights that have been claimed, for each staker address.
+    claimed: BTreeMap<String, u32>,
+    /// The claims that were submitted, but are not yet known to be accepted, for each staker address.
+    #[serde(default)]
+    unconfirmed: BTreeMap<String, Authorized<N>>,
+}
+
+/// A watcher that claims the unbonded microcredits of a set of stakers once the unbonding period has passed.
+pub struct ClaimWatcher {
+    /// The API endpoint to query and submit to.
+    endpoint: String,
+    /// The priority fee in microcredits for each claim.
+    priority_fee_in_microcredits: Microcredits,
+    /// The private keys of the watched stakers.
+    stakers: Vec<Zeroizing<PrivateKey<N>>>,
+    /// The claimed unbonding heights, and the claims that are not yet known to be accepted.
+    claims: Claims,
+    /// The path to persist the claims to, before and after each claim.
+    path: Option<PathBuf>,
+}
+
+impl ClaimWatcher {
+    /// Initializes a new watcher for the given API endpoint and priority fee.
+    pub fn new(endpoint: &str, priority_fee_in_microcredits: impl Into<Microcredits>) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            priority_fee_in_microcredits: priority_fee_in_microcredits.into(),
+            stakers: vec![],
+            claims: Default::default(),
+            path: None,
+        }
+    }
+
+    /// Persists the claims to the given path, loading any that were previously persisted.
+    ///
+    /// This ensures the watcher never claims the same unbonding amount twice, even across restarts.
+    pub fn persist_to(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            self.claims = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        }
+        self.path = Some(path.as_ref().to_path_buf());
+        Ok(self)
+    }
+
+    /// Adds the staker with the given private key to the watch list.
+    pub fn watch(&mut self, private_key: &str) -> Result<Address<N>> {
+        // Initialize the private key.
+        let private_key = parse_private_key(private_key)?;
+        // Derive the staker's address.
+        let address = Address::try_from(&*private_key)?;
+        // Add the staker, if it is not already watched.
+        if !self.stakers.contains(&private_key) {
+            self.stakers.push(private_key);
+        }
+        Ok(address)
+    }
+
+    /// Returns the addresses of the watched stakers.
+    pub fn stakers(&self) -> Result<Vec<Address<N>>> {
+        self.stakers.iter().map(|private_key| Address::try_from(&**private_key)).collect()
+    }
+
+    /// Returns the unbonding height that was last claimed for the given staker, if any.
+    pub fn claimed_height(&self, address: &Address<N>) -> Option<u32> {
+        self.claims.claimed.get(&address.to_string()).copied()
+    }
+
+    /// Checks every watched staker once, and claims any unbonding amount whose unbonding height has been reached.
+    ///
+    /// This method should be called periodically, i.e. once per block.
+    pub fn poll(&mut self, rng: &mut (impl Rng + CryptoRng)) -> Result<Vec<(Address<N>, ClaimStatus)>> {
+        // Retrieve the latest block height.
+        let latest_height = get_latest_height(&self.endpoint)?;
+
+        let mut statuses = Vec::with_capacity(self.stakers.len());
+        for index in 0..self.stakers.len() {
+            let address = Address::try_from(&*self.stakers[index])?;
+            let status = match self.check(index, &address, latest_height, rng) {
+                Ok(status) => status,
+                Err(error) => ClaimStatus::Failed(error.to_string()),
+            };
+            statuses.push((address, status));
+        }
+        Ok(statuses)
+    }
+
+    /// Checks the staker at the given index, and claims their unbonding amount if the unbonding height has been reached.
+    ///
+    /// The claim is recorded before it is submitted, so that a crash never leads to a second claim. If the claim
+    /// is not known to be accepted, i.e. after a timeout, it is looked up on chain on the next poll, and otherwise
+    /// resubmitted with the same authorization. The record is only reverted if the claim was rejected.
+    fn check(
+        &mut self,
+        index: usize,
+        address: &Address<N>,
+        latest_height: u32,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<ClaimStatus> {
+        let address = address.to_string();
+
+        // Retrieve the unbond state.
+        let unbond_state = match get_unbond_state(&self.endpoint, &address)? {
+            Some(unbond_state) => unbond_state,
+            None => return Ok(ClaimStatus::NotUnbonding),
+        };
+        // Ensure this unbonding amount has not already been claimed, and confirm its claim if it is not yet accepted.
+        if self.claims.claimed.get(&address) == Some(&unbond_state.height) {
+            return match self.claims.unconfirmed.get(&address).cloned() {
+                Some(authorization) => self.confirm(&address, authorization),
+                None => Ok(ClaimStatus::AlreadyClaimed),
+            };
+        }
+        // Ensure the unbonding height has been reached.
+        if latest_height < unbond_state.height {
+            return Ok(ClaimStatus::Pending(unbond_state.height));
+        }
+
+        // Authorize the claim.
+        let authorization = Credits::claim_unbond_public(
+            &Zeroizing::new(self.stakers[index].to_string()),
+            self.priority_fee_in_microcredits,
+            ExecutionMode::Broadcast,
+            rng,
+        )?;
+
+        // Record the claim before submitting it.
+        let previous = self.claims.claimed.insert(address.clone(), unbond_state.height);
+        self.claims.unconfirmed.insert(address.clone(), authorization.clone());
+        self.persist()?;
+
+        // Execute the claim.
+        match authorization.execute_with(&RetryPolicy::new(&self.endpoint)) {
+            Ok(transaction) => {
+                self.claims.unconfirmed.remove(&address);
+                self.persist()?;
+                Ok(ClaimStatus::Claimed(transaction.id().to_string()))
+            }
+            // Revert the record if the claim was rejected, so that the claim is retried on the next poll.
+            Err(error) if error.is::<RequestRejected>() => {
+                match previous {
+                    Some(height) => self.claims.claimed.insert(address.clone(), height),
+                    None => self.claims.claimed.remove(&address),
+                };
+                self.claims.unconfirmed.remove(&address);
+                self.persist()?;
+                Err(error)
+            }
+            // Otherwise, keep the record, as the claim may have been accepted.
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Looks up the given claim that is not yet known to be accepted, or resubmits it if it is not found on chain.
+    ///
+    /// If the resubmission fails, the claim is kept unconfirmed, as the network also rejects a known transaction.
+    fn confirm(&mut self, address: &str, authorization: Authorized<N>) -> Result<ClaimStatus> {
+        let transaction_id = match authorization.find_transaction_id(&self.endpoint)? {
+            Some(transaction_id) => transaction_id,
+            None => authorization.execute_with(&RetryPolicy::new(&self.endpoint))?.id().to_string(),
+        };
+        self.claims.unconfirmed.remove(address);
+        self.persist()?;
+        Ok(ClaimStatus::Claimed(transaction_id))
+    }
+
+    /// Persists the claims to the path, if one is set.
+    fn persist(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            write_atomically(path, &self.claims)?;
+        }
+        Ok(())
+    }
+}